@@ -57,52 +57,95 @@ impl Program {
             bail!("Too many program headers");
         }
         for segment in segments.iter().filter(|x| x.p_type == elf::abi::PT_LOAD) {
-            let file_size: u32 = segment
-                .p_filesz
-                .try_into()
-                .map_err(|err| anyhow!("filesize was larger than 32 bits. {err}"))?;
-            if file_size >= max_mem {
-                bail!("Invalid segment file_size");
-            }
-            let mem_size: u32 = segment
-                .p_memsz
-                .try_into()
-                .map_err(|err| anyhow!("mem_size was larger than 32 bits {err}"))?;
-            if mem_size >= max_mem {
-                bail!("Invalid segment mem_size");
-            }
-            let vaddr: u32 = segment
-                .p_vaddr
-                .try_into()
-                .map_err(|err| anyhow!("vaddr is larger than 32 bits. {err}"))?;
-            if vaddr % WORD_SIZE as u32 != 0 {
-                bail!("vaddr {vaddr:08x} is unaligned");
+            let segment = Segment::new(
+                segment.p_offset,
+                segment.p_vaddr,
+                segment.p_filesz,
+                segment.p_memsz,
+                max_mem,
+            )?;
+            segment.load(&mut image, max_mem, |i| {
+                input.get((segment.offset + i) as usize).copied()
+            })?;
+        }
+        Ok(Program { entry, image })
+    }
+}
+
+/// A validated loadable segment of an ELF file.
+pub(crate) struct Segment {
+    pub(crate) offset: u32,
+    pub(crate) vaddr: u32,
+    pub(crate) file_size: u32,
+    pub(crate) mem_size: u32,
+}
+
+impl Segment {
+    /// Check a loadable segment from its program header fields.
+    pub(crate) fn new(
+        p_offset: u64,
+        p_vaddr: u64,
+        p_filesz: u64,
+        p_memsz: u64,
+        max_mem: u32,
+    ) -> Result<Self> {
+        let file_size: u32 = p_filesz
+            .try_into()
+            .map_err(|err| anyhow!("filesize was larger than 32 bits. {err}"))?;
+        if file_size >= max_mem {
+            bail!("Invalid segment file_size");
+        }
+        let mem_size: u32 = p_memsz
+            .try_into()
+            .map_err(|err| anyhow!("mem_size was larger than 32 bits {err}"))?;
+        if mem_size >= max_mem {
+            bail!("Invalid segment mem_size");
+        }
+        let vaddr: u32 = p_vaddr
+            .try_into()
+            .map_err(|err| anyhow!("vaddr is larger than 32 bits. {err}"))?;
+        if vaddr % WORD_SIZE as u32 != 0 {
+            bail!("vaddr {vaddr:08x} is unaligned");
+        }
+        let offset: u32 = p_offset
+            .try_into()
+            .map_err(|err| anyhow!("offset is larger than 32 bits. {err}"))?;
+        Ok(Self {
+            offset,
+            vaddr,
+            file_size,
+            mem_size,
+        })
+    }
+
+    /// Write the words of this segment into `image`.
+    ///
+    /// `read` returns the byte at the given position in the segment's file data, if present.
+    pub(crate) fn load(
+        &self,
+        image: &mut BTreeMap<u32, u32>,
+        max_mem: u32,
+        read: impl Fn(u32) -> Option<u8>,
+    ) -> Result<()> {
+        for i in (0..self.mem_size).step_by(WORD_SIZE) {
+            let addr = self.vaddr.checked_add(i).context("Invalid segment vaddr")?;
+            if addr >= max_mem {
+                bail!("Address [0x{addr:08x}] exceeds maximum address for guest programs [0x{max_mem:08x}]");
             }
-            let offset: u32 = segment
-                .p_offset
-                .try_into()
-                .map_err(|err| anyhow!("offset is larger than 32 bits. {err}"))?;
-            for i in (0..mem_size).step_by(WORD_SIZE) {
-                let addr = vaddr.checked_add(i).context("Invalid segment vaddr")?;
-                if addr >= max_mem {
-                    bail!("Address [0x{addr:08x}] exceeds maximum address for guest programs [0x{max_mem:08x}]");
-                }
-                if i >= file_size {
-                    // Past the file size, all zeros.
-                    image.insert(addr, 0);
-                } else {
-                    let mut word = 0;
-                    // Don't read past the end of the file.
-                    let len = core::cmp::min(file_size - i, WORD_SIZE as u32);
-                    for j in 0..len {
-                        let offset = (offset + i + j) as usize;
-                        let byte = input.get(offset).context("Invalid segment offset")?;
-                        word |= (*byte as u32) << (j * 8);
-                    }
-                    image.insert(addr, word);
+            if i >= self.file_size {
+                // Past the file size, all zeros.
+                image.insert(addr, 0);
+            } else {
+                let mut word = 0;
+                // Don't read past the end of the file.
+                let len = core::cmp::min(self.file_size - i, WORD_SIZE as u32);
+                for j in 0..len {
+                    let byte = read(i + j).context("Invalid segment offset")?;
+                    word |= (byte as u32) << (j * 8);
                 }
+                image.insert(addr, word);
             }
         }
-        Ok(Program { entry, image })
+        Ok(())
     }
 }
@@ -0,0 +1,229 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use anyhow::{bail, ensure, Result};
+use risc0_zkp::core::digest::Digest;
+use risc0_zkvm_platform::{memory::GUEST_MAX_MEM, PAGE_SIZE, WORD_SIZE};
+
+use crate::{elf::Segment, MemoryImage, Program};
+
+/// Size of the header of a 32-bit ELF file.
+const EHDR_SIZE: usize = 52;
+
+/// Size of a program header of a 32-bit ELF file.
+const PHDR_SIZE: usize = 32;
+
+/// Computes the image ID of an ELF binary that is provided in chunks.
+///
+/// This produces the same image ID as [crate::compute_image_id], without holding the whole ELF
+/// in memory: only the bytes of the loadable segments are kept, so sections such as debug info
+/// are discarded as they are streamed in. This assumes the program header table appears before
+/// the segments in the file, as it does in ELF files produced by the usual linkers; otherwise the
+/// ELF is buffered until the program headers have been read.
+///
+/// ```rust,ignore
+/// use risc0_binfmt::ImageIdHasher;
+///
+/// let mut hasher = ImageIdHasher::new();
+/// for chunk in elf.chunks(4096) {
+///     hasher.update(chunk)?;
+/// }
+/// let image_id = hasher.finalize()?;
+/// ```
+#[derive(Default)]
+pub struct ImageIdHasher {
+    /// Bytes received before the program headers could be parsed.
+    pending: Vec<u8>,
+
+    /// Number of bytes received so far.
+    len: u64,
+
+    /// The parsed headers, once available.
+    headers: Option<Headers>,
+}
+
+struct Headers {
+    entry: u32,
+    segments: Vec<(Segment, Vec<u8>)>,
+}
+
+impl ImageIdHasher {
+    /// Construct a new hasher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process the next chunk of the ELF.
+    ///
+    /// Returns an error as soon as the ELF or program headers are found to be invalid.
+    pub fn update(&mut self, bytes: &[u8]) -> Result<()> {
+        let start = self.len;
+        self.len += bytes.len() as u64;
+        match &mut self.headers {
+            Some(headers) => headers.copy(start, bytes),
+            None => {
+                self.pending.extend_from_slice(bytes);
+                if let Some(mut headers) = Headers::parse(&self.pending)? {
+                    headers.copy(0, &core::mem::take(&mut self.pending));
+                    self.headers = Some(headers);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Finish processing the ELF and return its image ID.
+    pub fn finalize(self) -> Result<Digest> {
+        let Some(headers) = self.headers else {
+            bail!("Elf parse error: truncated ELF header or program header table");
+        };
+        let max_mem = GUEST_MAX_MEM as u32;
+        let mut image = BTreeMap::new();
+        for (segment, data) in headers.segments.iter() {
+            segment.load(&mut image, max_mem, |i| data.get(i as usize).copied())?;
+        }
+        let program = Program {
+            entry: headers.entry,
+            image,
+        };
+        let image = MemoryImage::new(&program, PAGE_SIZE as u32)?;
+        Ok(image.compute_id())
+    }
+}
+
+impl Headers {
+    /// Parse the ELF header and program headers from the start of the ELF, or return `None` if
+    /// more bytes are needed.
+    fn parse(input: &[u8]) -> Result<Option<Self>> {
+        if input.len() < EHDR_SIZE {
+            return Ok(None);
+        }
+        let u16_at = |offset: usize| u16::from_le_bytes([input[offset], input[offset + 1]]);
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes(input[offset..offset + WORD_SIZE].try_into().unwrap())
+        };
+
+        ensure!(
+            input[..4] == b"\x7fELF"[..],
+            "Elf parse error: invalid ELF magic"
+        );
+        if input[4] != elf::abi::ELFCLASS32 {
+            bail!("Not a 32-bit ELF");
+        }
+        ensure!(
+            input[5] == elf::abi::ELFDATA2LSB,
+            "Elf parse error: ELF is not little endian"
+        );
+        if u16_at(18) != elf::abi::EM_RISCV {
+            bail!("Invalid machine type, must be RISC-V");
+        }
+        if u16_at(16) != elf::abi::ET_EXEC {
+            bail!("Invalid ELF type, must be executable");
+        }
+        let max_mem = GUEST_MAX_MEM as u32;
+        let entry = u32_at(24);
+        if entry >= max_mem || entry % WORD_SIZE as u32 != 0 {
+            bail!("Invalid entrypoint");
+        }
+
+        let phoff = u32_at(28) as usize;
+        let phentsize = u16_at(42) as usize;
+        let phnum = u16_at(44) as usize;
+        if phoff == 0 || phnum == 0 {
+            bail!("Missing segment table");
+        }
+        ensure!(
+            phentsize == PHDR_SIZE,
+            "Elf parse error: invalid program header size {phentsize}"
+        );
+        if phnum > 256 {
+            bail!("Too many program headers");
+        }
+        let table_end = phoff + phnum * PHDR_SIZE;
+        if input.len() < table_end {
+            return Ok(None);
+        }
+
+        let mut segments = Vec::new();
+        for phdr in input[phoff..table_end].chunks_exact(PHDR_SIZE) {
+            let field = |idx: usize| {
+                u32::from_le_bytes(phdr[idx * WORD_SIZE..][..WORD_SIZE].try_into().unwrap()) as u64
+            };
+            if field(0) != elf::abi::PT_LOAD as u64 {
+                continue;
+            }
+            let segment = Segment::new(field(1), field(2), field(4), field(5), max_mem)?;
+            segments.push((segment, Vec::new()));
+        }
+        Ok(Some(Self { entry, segments }))
+    }
+
+    /// Copy the bytes of `chunk`, which starts at `start` in the ELF, that belong to a segment.
+    fn copy(&mut self, start: u64, chunk: &[u8]) {
+        let end = start + chunk.len() as u64;
+        for (segment, data) in self.segments.iter_mut() {
+            // Bytes are received in order, so this is the first byte of the segment still missing.
+            let next = segment.offset as u64 + data.len() as u64;
+            let segment_end = segment.offset as u64 + segment.file_size as u64;
+            if next >= end.min(segment_end) {
+                continue;
+            }
+            let from = (next - start) as usize;
+            let to = (end.min(segment_end) - start) as usize;
+            data.extend_from_slice(&chunk[from..to]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risc0_zkvm_methods::MULTI_TEST_ELF;
+
+    use super::ImageIdHasher;
+    use crate::compute_image_id;
+
+    #[test]
+    fn matches_compute_image_id() {
+        let expected = compute_image_id(MULTI_TEST_ELF).unwrap();
+        for chunk_size in [1, 7, 4096, MULTI_TEST_ELF.len()] {
+            let mut hasher = ImageIdHasher::new();
+            for chunk in MULTI_TEST_ELF.chunks(chunk_size) {
+                hasher.update(chunk).unwrap();
+            }
+            assert_eq!(
+                hasher.finalize().unwrap(),
+                expected,
+                "chunk size {chunk_size}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_elf() {
+        // The ELF header is incomplete.
+        let mut hasher = ImageIdHasher::new();
+        hasher.update(&MULTI_TEST_ELF[..16]).unwrap();
+        assert!(hasher.finalize().is_err());
+
+        // Change the machine type to x86-64.
+        let mut elf = MULTI_TEST_ELF.to_vec();
+        elf[18..20].copy_from_slice(&62u16.to_le_bytes());
+        let err = ImageIdHasher::new().update(&elf).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid machine type, must be RISC-V");
+    }
+}
@@ -24,10 +24,15 @@ mod exit_code;
 mod hash;
 #[cfg(not(target_os = "zkvm"))]
 mod image;
+#[cfg(not(target_os = "zkvm"))]
+mod image_id;
 mod sys_state;
 
 #[cfg(not(target_os = "zkvm"))]
-pub use self::image::{MemoryImage, PageTableInfo};
+pub use self::{
+    image::{MemoryImage, PageTableInfo},
+    image_id::ImageIdHasher,
+};
 pub use crate::{
     elf::Program,
     exit_code::{ExitCode, InvalidExitCodeError},
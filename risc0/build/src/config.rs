@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::path::PathBuf;
+use std::{fmt, path::PathBuf, str::FromStr};
 
+use anyhow::{bail, Result};
 use cargo_metadata::Package;
 use serde::{Deserialize, Serialize};
 
@@ -35,6 +36,201 @@ pub struct GuestOptions {
 
     /// Use a docker environment for building.
     pub use_docker: Option<DockerOptions>,
+
+    /// Value of `SOURCE_DATE_EPOCH` to build the guest with, in seconds since the Unix epoch.
+    ///
+    /// When `None`, the commit time of the git repository containing the guest is used, if
+    /// available.
+    pub source_date_epoch: Option<u64>,
+
+    /// Pass the rustc flags of the enclosing build, from `CARGO_ENCODED_RUSTFLAGS` or
+    /// `RUSTFLAGS`, on to the guest build.
+    ///
+    /// By default these flags are not inherited, since flags meant for the host, such as
+    /// `-C instrument-coverage`, would otherwise change the guest ELF and its image ID.
+    pub inherit_rustflags: bool,
+
+    /// The zkVM variant to build the guest for.
+    pub target: GuestTarget,
+
+    /// Link-time optimization mode to build the guest with.
+    ///
+    /// When `None`, the setting of the guest's cargo profile is used. Changing this changes the
+    /// guest ELF, and therefore its image ID.
+    pub lto: Option<LtoMode>,
+
+    /// Number of codegen units to build the guest with.
+    ///
+    /// When `None`, the setting of the guest's cargo profile is used. A value of `1` usually
+    /// results in smaller code and fewer cycles, at the cost of a slower build. Changing this
+    /// changes the guest ELF, and therefore its image ID.
+    pub codegen_units: Option<u32>,
+
+    /// How the guest handles panics.
+    pub panic_strategy: PanicStrategy,
+
+    /// Syscalls the guest must not invoke, e.g. `SYS_RANDOM`.
+    ///
+    /// The build fails if any of these syscalls is referenced by the guest ELF.
+    pub forbidden_syscalls: Vec<String>,
+}
+
+/// Link-time optimization modes for a guest build, as in the `lto` setting of a cargo profile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LtoMode {
+    /// Disable link-time optimization.
+    Off,
+
+    /// Thin local LTO, as with `lto = false`.
+    ThinLocal,
+
+    /// Thin LTO across all crates.
+    Thin,
+
+    /// Fat LTO across all crates.
+    Fat,
+}
+
+impl LtoMode {
+    /// Returns the value of the cargo profile `lto` setting for this mode.
+    pub(crate) fn profile_value(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::ThinLocal => "false",
+            Self::Thin => "thin",
+            Self::Fat => "fat",
+        }
+    }
+}
+
+impl fmt::Display for LtoMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::ThinLocal => write!(f, "thin-local"),
+            Self::Thin => write!(f, "thin"),
+            Self::Fat => write!(f, "fat"),
+        }
+    }
+}
+
+impl FromStr for LtoMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "off" => Ok(Self::Off),
+            "thin-local" => Ok(Self::ThinLocal),
+            "thin" => Ok(Self::Thin),
+            "fat" => Ok(Self::Fat),
+            _ => bail!("unknown LTO mode `{s}`, expected `off`, `thin-local`, `thin` or `fat`"),
+        }
+    }
+}
+
+/// The zkVM variant a guest is built for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GuestTarget {
+    /// 32-bit RISC-V, using the `riscv32im-risc0-zkvm-elf` target.
+    #[default]
+    Rv32,
+
+    /// 64-bit RISC-V.
+    ///
+    /// The zkVM does not currently support 64-bit guests, and building for this target fails with
+    /// an error.
+    Rv64,
+}
+
+impl GuestTarget {
+    /// Returns the rustc target triple for guests built for this target, or an error if the zkVM
+    /// does not support it.
+    pub fn triple(&self) -> Result<&'static str> {
+        match self {
+            Self::Rv32 => Ok("riscv32im-risc0-zkvm-elf"),
+            Self::Rv64 => {
+                bail!("unsupported guest target `{self}`: the zkVM only supports 32-bit guests")
+            }
+        }
+    }
+}
+
+impl fmt::Display for GuestTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rv32 => write!(f, "rv32"),
+            Self::Rv64 => write!(f, "rv64"),
+        }
+    }
+}
+
+impl FromStr for GuestTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "rv32" => Ok(Self::Rv32),
+            "rv64" => Ok(Self::Rv64),
+            _ => bail!("unknown guest target `{s}`, expected `rv32` or `rv64`"),
+        }
+    }
+}
+
+/// How a guest handles panics, as in the `panic` setting of a cargo profile.
+///
+/// The panic strategy is part of the compiled guest, so it affects the image ID.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PanicStrategy {
+    /// Halt the guest with an error as soon as it panics.
+    #[default]
+    Abort,
+
+    /// Unwind the stack on panic, so that panics can be caught with `std::panic::catch_unwind`.
+    ///
+    /// The zkVM target does not currently support unwinding, as its standard library is only
+    /// built with the abort panic runtime, and building with this strategy fails with an error.
+    /// Unwinding would also add landing pads to the guest, increasing its size and cycle count.
+    Unwind,
+}
+
+impl PanicStrategy {
+    /// Returns an error if the zkVM does not support this strategy.
+    ///
+    /// Guests are always compiled with `-C panic=abort`, which is the only strategy supported
+    /// today.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::Abort => Ok(()),
+            Self::Unwind => bail!(
+                "unsupported panic strategy `{self}`: the zkVM target has no unwinder, so guests \
+                 must be built with `abort`"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for PanicStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Abort => write!(f, "abort"),
+            Self::Unwind => write!(f, "unwind"),
+        }
+    }
+}
+
+impl FromStr for PanicStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "abort" => Ok(Self::Abort),
+            "unwind" => Ok(Self::Unwind),
+            _ => bail!("unknown panic strategy `{s}`, expected `abort` or `unwind`"),
+        }
+    }
 }
 
 /// Metadata defining options to build a guest
@@ -66,6 +262,27 @@ pub(crate) struct GuestBuildOptions {
 
     /// Configuration flags to build the guest with.
     pub(crate) rustc_flags: Vec<String>,
+
+    /// Value of `SOURCE_DATE_EPOCH` to build the guest with.
+    pub(crate) source_date_epoch: Option<u64>,
+
+    /// Pass the rustc flags of the enclosing build on to the guest build.
+    pub(crate) inherit_rustflags: bool,
+
+    /// The zkVM variant to build the guest for.
+    pub(crate) target: GuestTarget,
+
+    /// Link-time optimization mode to build the guest with.
+    pub(crate) lto: Option<LtoMode>,
+
+    /// Number of codegen units to build the guest with.
+    pub(crate) codegen_units: Option<u32>,
+
+    /// How the guest handles panics.
+    pub(crate) panic_strategy: PanicStrategy,
+
+    /// Syscalls the guest must not invoke.
+    pub(crate) forbidden_syscalls: Vec<String>,
 }
 
 impl From<GuestOptions> for GuestBuildOptions {
@@ -73,6 +290,13 @@ impl From<GuestOptions> for GuestBuildOptions {
         Self {
             features: value.features,
             use_docker: value.use_docker,
+            source_date_epoch: value.source_date_epoch,
+            inherit_rustflags: value.inherit_rustflags,
+            target: value.target,
+            lto: value.lto,
+            codegen_units: value.codegen_units,
+            panic_strategy: value.panic_strategy,
+            forbidden_syscalls: value.forbidden_syscalls,
             ..Default::default()
         }
     }
@@ -83,4 +307,90 @@ impl GuestBuildOptions {
         self.rustc_flags = metadata.rustc_flags.unwrap_or_default();
         self
     }
+
+    /// Environment variables overriding the settings of the given cargo profile, e.g. `release`.
+    pub(crate) fn profile_env(&self, profile: &str) -> Vec<(String, String)> {
+        let prefix = format!("CARGO_PROFILE_{}", profile.to_uppercase());
+        let mut env = Vec::new();
+        if let Some(lto) = self.lto {
+            env.push((format!("{prefix}_LTO"), lto.profile_value().to_string()));
+        }
+        if let Some(units) = self.codegen_units {
+            env.push((format!("{prefix}_CODEGEN_UNITS"), units.to_string()));
+        }
+        env
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GuestBuildOptions, GuestOptions, GuestTarget, LtoMode, PanicStrategy};
+
+    #[test]
+    fn guest_target() {
+        assert_eq!(GuestTarget::default(), GuestTarget::Rv32);
+        assert_eq!(
+            GuestTarget::Rv32.triple().unwrap(),
+            "riscv32im-risc0-zkvm-elf"
+        );
+
+        let err = GuestTarget::Rv64.triple().unwrap_err();
+        assert!(err.to_string().contains("unsupported guest target `rv64`"));
+
+        for target in [GuestTarget::Rv32, GuestTarget::Rv64] {
+            assert_eq!(target.to_string().parse::<GuestTarget>().unwrap(), target);
+        }
+        assert!("riscv64".parse::<GuestTarget>().is_err());
+    }
+
+    #[test]
+    fn panic_strategy() {
+        assert_eq!(PanicStrategy::default(), PanicStrategy::Abort);
+        PanicStrategy::Abort.validate().unwrap();
+
+        let err = PanicStrategy::Unwind.validate().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unsupported panic strategy `unwind`"));
+
+        for strategy in [PanicStrategy::Abort, PanicStrategy::Unwind] {
+            assert_eq!(
+                strategy.to_string().parse::<PanicStrategy>().unwrap(),
+                strategy
+            );
+        }
+        assert!("catch".parse::<PanicStrategy>().is_err());
+    }
+
+    #[test]
+    fn profile_env() {
+        let opts = GuestBuildOptions::from(GuestOptions::default());
+        assert!(opts.profile_env("release").is_empty());
+
+        let opts = GuestBuildOptions::from(GuestOptions {
+            lto: Some(LtoMode::Fat),
+            codegen_units: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(
+            opts.profile_env("release"),
+            [
+                ("CARGO_PROFILE_RELEASE_LTO".to_string(), "fat".to_string()),
+                (
+                    "CARGO_PROFILE_RELEASE_CODEGEN_UNITS".to_string(),
+                    "1".to_string()
+                ),
+            ]
+        );
+
+        for mode in [
+            LtoMode::Off,
+            LtoMode::ThinLocal,
+            LtoMode::Thin,
+            LtoMode::Fat,
+        ] {
+            assert_eq!(mode.to_string().parse::<LtoMode>().unwrap(), mode);
+        }
+        assert!("full".parse::<LtoMode>().is_err());
+    }
 }
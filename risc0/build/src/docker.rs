@@ -21,7 +21,10 @@ use risc0_binfmt::{MemoryImage, Program};
 use risc0_zkvm_platform::{memory::GUEST_MAX_MEM, PAGE_SIZE};
 use tempfile::tempdir;
 
-use crate::{config::GuestBuildOptions, encode_rust_flags, get_env_var, GuestOptions};
+use crate::{
+    config::GuestBuildOptions, encode_rust_flags, get_env_var, inherited_rust_flags,
+    source_date_epoch, syscalls::check_forbidden_syscalls, GuestOptions,
+};
 
 const DOCKER_IGNORE: &str = r#"
 **/Dockerfile
@@ -56,6 +59,8 @@ pub(crate) fn build_guest_package_docker(
     src_dir: &Path,
     guest_opts: &GuestBuildOptions,
 ) -> Result<BuildStatus> {
+    let target = guest_opts.target.triple()?;
+    guest_opts.panic_strategy.validate()?;
     if !get_env_var("RISC0_SKIP_BUILD").is_empty() {
         eprintln!("Skipping build because RISC0_SKIP_BUILD is set");
         return Ok(BuildStatus::Skipped);
@@ -73,7 +78,7 @@ pub(crate) fn build_guest_package_docker(
     let pkg_name = &root_pkg.name;
 
     eprintln!("Docker context: {src_dir:?}");
-    eprintln!("Building ELF binaries in {pkg_name} for riscv32im-risc0-zkvm-elf target...");
+    eprintln!("Building ELF binaries in {pkg_name} for {target} target...");
 
     if !Command::new("docker")
         .arg("--version")
@@ -93,16 +98,24 @@ pub(crate) fn build_guest_package_docker(
         let temp_dir = tempdir()?;
         let temp_path = temp_dir.path();
         let rel_manifest_path = manifest_path.strip_prefix(&src_dir)?;
-        create_dockerfile(rel_manifest_path, temp_path, pkg_name.as_str(), guest_opts)?;
+        let source_date_epoch = source_date_epoch(guest_opts, manifest_path.parent().unwrap());
+        create_dockerfile(
+            rel_manifest_path,
+            temp_path,
+            pkg_name.as_str(),
+            guest_opts,
+            source_date_epoch,
+        )?;
         build(&src_dir, temp_path)?;
     }
     println!("ELFs ready at:");
 
     let target_dir = src_dir.join(TARGET_DIR);
-    for target in root_pkg.targets.iter().filter(|t| t.is_bin()) {
-        let elf_path = target_dir.join(&pkg_name).join(&target.name);
+    for bin in root_pkg.targets.iter().filter(|t| t.is_bin()) {
+        let elf_path = target_dir.join(&pkg_name).join(&bin.name);
+        check_forbidden_syscalls(&elf_path, &guest_opts.forbidden_syscalls)?;
         let image_id = compute_image_id(&elf_path)?;
-        let rel_elf_path = Path::new(TARGET_DIR).join(&pkg_name).join(&target.name);
+        let rel_elf_path = Path::new(TARGET_DIR).join(&pkg_name).join(&bin.name);
         println!("ImageID: {} - {:?}", image_id, rel_elf_path);
     }
 
@@ -117,21 +130,23 @@ fn create_dockerfile(
     temp_dir: &Path,
     pkg_name: &str,
     guest_opts: &GuestBuildOptions,
+    source_date_epoch: Option<u64>,
 ) -> Result<()> {
     let manifest_env = &[("CARGO_MANIFEST_PATH", manifest_path.to_str().unwrap())];
     let encoded_rust_flags = encode_rust_flags(
-        &guest_opts
-            .rustc_flags
+        &inherited_rust_flags(guest_opts)
             .iter()
+            .chain(&guest_opts.rustc_flags)
             .map(|s| s.as_str())
             .collect::<Vec<_>>(),
     );
     let rustflags_env = &[("CARGO_ENCODED_RUSTFLAGS", encoded_rust_flags.as_str())];
 
+    let target = guest_opts.target.triple()?;
     let common_args = vec![
         "--locked",
         "--target",
-        "riscv32im-risc0-zkvm-elf",
+        target,
         "--manifest-path",
         "$CARGO_MANIFEST_PATH",
     ];
@@ -161,6 +176,13 @@ fn create_dockerfile(
         .env(rustflags_env)
         .env(&[("CARGO_TARGET_DIR", "target")]);
 
+    if let Some(epoch) = source_date_epoch {
+        build = build.env(&[("SOURCE_DATE_EPOCH", epoch.to_string().as_str())]);
+    }
+    for (key, value) in guest_opts.profile_env("release") {
+        build = build.env(&[(key.as_str(), value.as_str())]);
+    }
+
     #[cfg(feature = "unstable")]
     {
         build = build.env(&[("RISC0_FEATURE_bigint2", "")]);
@@ -178,14 +200,11 @@ fn create_dockerfile(
         .run(&build_cmd);
 
     let out_dir = format!("/{pkg_name}");
+    let build_dir = format!("/src/target/{target}/release");
     let binary = DockerFile::new()
         .comment("export stage")
         .from_alias("export", "scratch")
-        .copy_from(
-            "build",
-            "/src/target/riscv32im-risc0-zkvm-elf/release",
-            out_dir.as_str(),
-        );
+        .copy_from("build", build_dir.as_str(), out_dir.as_str());
 
     let file = DockerFile::new().dockerfile(build).dockerfile(binary);
     fs::write(temp_dir.join("Dockerfile"), file.to_string())?;
@@ -20,6 +20,7 @@
 
 mod config;
 mod docker;
+mod syscalls;
 
 use std::{
     borrow::Cow,
@@ -42,10 +43,11 @@ use serde::Deserialize;
 use self::{
     config::{GuestBuildOptions, GuestMetadata},
     docker::build_guest_package_docker,
+    syscalls::check_forbidden_syscalls,
 };
 
 pub use self::{
-    config::{DockerOptions, GuestOptions},
+    config::{DockerOptions, GuestOptions, GuestTarget, LtoMode, PanicStrategy},
     docker::{docker_build, BuildStatus, TARGET_DIR},
 };
 
@@ -337,9 +339,13 @@ fn get_env_var(name: &str) -> String {
 fn guest_methods<G: GuestBuilder>(
     pkg: &Package,
     target_dir: impl AsRef<Path>,
-    guest_features: &[String],
+    guest_opts: &GuestBuildOptions,
 ) -> Vec<G> {
     let profile = if is_debug() { "debug" } else { "release" };
+    let triple = guest_opts
+        .target
+        .triple()
+        .unwrap_or_else(|err| panic!("{}: {err}", pkg.name));
     pkg.targets
         .iter()
         .filter(|target| target.kind.iter().any(|kind| kind == "bin"))
@@ -347,14 +353,14 @@ fn guest_methods<G: GuestBuilder>(
             target
                 .required_features
                 .iter()
-                .all(|required_feature| guest_features.contains(required_feature))
+                .all(|required_feature| guest_opts.features.contains(required_feature))
         })
         .map(|target| {
             G::build(
                 &target.name,
                 target_dir
                     .as_ref()
-                    .join("riscv32im-risc0-zkvm-elf")
+                    .join(triple)
                     .join(profile)
                     .join(&target.name)
                     .to_str()
@@ -367,11 +373,19 @@ fn guest_methods<G: GuestBuilder>(
 }
 
 /// Returns all methods associated with the given guest crate.
-fn guest_methods_docker<P, G>(pkg: &Package, target_dir: P) -> Vec<G>
+fn guest_methods_docker<P, G>(
+    pkg: &Package,
+    target_dir: P,
+    guest_opts: &GuestBuildOptions,
+) -> Vec<G>
 where
     P: AsRef<Path>,
     G: GuestBuilder,
 {
+    let triple = guest_opts
+        .target
+        .triple()
+        .unwrap_or_else(|err| panic!("{}: {err}", pkg.name));
     pkg.targets
         .iter()
         .filter(|target| target.kind.iter().any(|kind| kind == "bin"))
@@ -380,7 +394,7 @@ where
                 &target.name,
                 target_dir
                     .as_ref()
-                    .join("riscv32im-risc0-zkvm-elf")
+                    .join(triple)
                     .join("docker")
                     .join(pkg.name.replace('-', "_"))
                     .join(&target.name)
@@ -393,7 +407,7 @@ where
         .collect()
 }
 
-/// Build a [Command] with CARGO and RUSTUP_TOOLCHAIN environment variables
+/// Build a [Command] with CARGO, RUSTUP_TOOLCHAIN, and host rustc flag environment variables
 /// removed.
 fn sanitized_cmd(tool: &str) -> Command {
     let mut cmd = Command::new(tool);
@@ -401,12 +415,39 @@ fn sanitized_cmd(tool: &str) -> Command {
         cmd.env_remove(key);
     }
     cmd.env_remove("RUSTUP_TOOLCHAIN");
+    // Flags and wrappers meant for the host build, e.g. for coverage instrumentation, must not
+    // change the guest ELF. See `GuestOptions::inherit_rustflags` to opt back in.
+    cmd.env_remove("RUSTFLAGS");
+    cmd.env_remove("RUSTC_WORKSPACE_WRAPPER");
     cmd
 }
 
+/// Returns the rustc flags of the enclosing build, if `guest_opts` asks to inherit them.
+pub(crate) fn inherited_rust_flags(guest_opts: &GuestBuildOptions) -> Vec<String> {
+    if !guest_opts.inherit_rustflags {
+        return Vec::new();
+    }
+    if let Ok(flags) = env::var("CARGO_ENCODED_RUSTFLAGS") {
+        return flags
+            .split('\x1f')
+            .filter(|flag| !flag.is_empty())
+            .map(String::from)
+            .collect();
+    }
+    env::var("RUSTFLAGS")
+        .map(|flags| flags.split_whitespace().map(String::from).collect())
+        .unwrap_or_default()
+}
+
 /// Creates a std::process::Command to execute the given cargo
 /// command in an environment suitable for targeting the zkvm guest.
 pub fn cargo_command(subcmd: &str, rust_flags: &[&str]) -> Command {
+    cargo_command_for_target(subcmd, rust_flags, GuestTarget::default().triple().unwrap())
+}
+
+/// Like [cargo_command], but builds for the given target triple, e.g. from
+/// [GuestTarget::triple].
+pub fn cargo_command_for_target(subcmd: &str, rust_flags: &[&str], target: &str) -> Command {
     let rustc = sanitized_cmd("rustup")
         .args(["+risc0", "which", "rustc"])
         .output()
@@ -418,7 +459,7 @@ pub fn cargo_command(subcmd: &str, rust_flags: &[&str]) -> Command {
     println!("Using rustc: {rustc}");
 
     let mut cmd = sanitized_cmd("cargo");
-    let mut args = vec![subcmd, "--target", "riscv32im-risc0-zkvm-elf"];
+    let mut args = vec![subcmd, "--target", target];
 
     if std::env::var("RISC0_BUILD_LOCKED").is_ok() {
         args.push("--locked");
@@ -602,6 +643,14 @@ fn build_guest_package<P>(
 ) where
     P: AsRef<Path>,
 {
+    let triple = guest_opts
+        .target
+        .triple()
+        .unwrap_or_else(|err| panic!("{}: {err}", pkg.name));
+    guest_opts
+        .panic_strategy
+        .validate()
+        .unwrap_or_else(|err| panic!("{}: {err}", pkg.name));
     if is_skip_build() {
         return;
     }
@@ -611,16 +660,23 @@ fn build_guest_package<P>(
     let runtime_rust_flags = runtime_lib
         .map(|lib| vec![String::from("-C"), format!("link_arg={}", lib)])
         .unwrap_or_default();
+    let inherited_rust_flags = inherited_rust_flags(guest_opts);
     let rust_flags: Vec<_> = [
-        runtime_rust_flags
+        inherited_rust_flags
             .iter()
             .map(|s| s.as_str())
             .collect::<Vec<_>>(),
+        runtime_rust_flags.iter().map(|s| s.as_str()).collect(),
         guest_opts.rustc_flags.iter().map(|s| s.as_str()).collect(),
     ]
     .concat();
 
-    let mut cmd = cargo_command("build", &rust_flags);
+    let mut cmd = cargo_command_for_target("build", &rust_flags, triple);
+
+    if let Some(epoch) = source_date_epoch(guest_opts, pkg.manifest_path.parent().unwrap()) {
+        cmd.env("SOURCE_DATE_EPOCH", epoch.to_string());
+    }
+    cmd.envs(guest_opts.profile_env(if is_debug() { "dev" } else { "release" }));
 
     let features_str = guest_opts.features.join(",");
     if !features_str.is_empty() {
@@ -644,10 +700,7 @@ fn build_guest_package<P>(
         .expect("cargo build failed");
     let stderr = child.stderr.take().unwrap();
 
-    tty_println(&format!(
-        "{}: Starting build for riscv32im-risc0-zkvm-elf",
-        pkg.name
-    ));
+    tty_println(&format!("{}: Starting build for {triple}", pkg.name));
 
     for line in BufReader::new(stderr).lines() {
         tty_println(&format!("{}: {}", pkg.name, line.unwrap()));
@@ -657,6 +710,47 @@ fn build_guest_package<P>(
     if !res.success() {
         std::process::exit(res.code().unwrap());
     }
+
+    let profile = if is_debug() { "debug" } else { "release" };
+    let built_bins = pkg.targets.iter().filter(|target| {
+        target.is_bin()
+            && target
+                .required_features
+                .iter()
+                .all(|feature| guest_opts.features.contains(feature))
+    });
+    for bin in built_bins {
+        let elf_path = target_dir
+            .as_ref()
+            .join(triple)
+            .join(profile)
+            .join(&bin.name);
+        check_forbidden_syscalls(&elf_path, &guest_opts.forbidden_syscalls)
+            .unwrap_or_else(|err| panic!("{}: {err}", pkg.name));
+    }
+}
+
+/// Returns the `SOURCE_DATE_EPOCH` to build a guest with.
+///
+/// Uses the value from `guest_opts` if set, and otherwise the time of the latest commit of the
+/// git repository containing `dir`, if any.
+pub(crate) fn source_date_epoch(
+    guest_opts: &GuestBuildOptions,
+    dir: impl AsRef<Path>,
+) -> Option<u64> {
+    if guest_opts.source_date_epoch.is_some() {
+        return guest_opts.source_date_epoch;
+    }
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ct"])
+        .current_dir(dir)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()?.trim().parse().ok()
 }
 
 fn detect_toolchain(name: &str) {
@@ -774,11 +868,11 @@ fn do_embed_methods<G: GuestBuilder>(
                 &guest_build_opts,
             )
             .unwrap();
-            guest_methods_docker(&guest_pkg, get_out_dir())
+            guest_methods_docker(&guest_pkg, get_out_dir(), &guest_build_opts)
         } else {
             let guest_dir = get_guest_dir(&pkg.name, &guest_pkg.name);
             build_guest_package(&guest_pkg, &guest_dir, &guest_build_opts, None);
-            guest_methods(&guest_pkg, &guest_dir, &guest_build_opts.features)
+            guest_methods(&guest_pkg, &guest_dir, &guest_build_opts)
         };
 
         for method in methods {
@@ -0,0 +1,81 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+
+/// Fail if the guest ELF at `elf_path` invokes any of the `forbidden` syscalls.
+///
+/// Syscalls are invoked by name, and `declare_syscall!` embeds each name in the ELF as a
+/// nul-terminated string qualified by its module path, e.g.
+/// `risc0_zkvm_platform::syscall::nr::SYS_RANDOM`. The linker discards the name unless code that
+/// invokes the syscall is linked in, so a guest that does not reference the name cannot invoke
+/// the syscall. Names may be given in full, or as the last path segment, e.g. `SYS_RANDOM`.
+pub(crate) fn check_forbidden_syscalls(elf_path: &Path, forbidden: &[String]) -> Result<()> {
+    if forbidden.is_empty() {
+        return Ok(());
+    }
+    let elf = fs::read(elf_path).with_context(|| format!("failed to read {elf_path:?}"))?;
+    let found: Vec<_> = forbidden
+        .iter()
+        .filter(|name| references_syscall(&elf, name))
+        .map(|name| name.as_str())
+        .collect();
+    if !found.is_empty() {
+        bail!(
+            "{elf_path:?} invokes forbidden syscalls: {}",
+            found.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn references_syscall(elf: &[u8], name: &str) -> bool {
+    let needle = [name.as_bytes(), b"\0"].concat();
+    let qualified = name.contains("::");
+    elf.windows(needle.len())
+        .enumerate()
+        .filter(|(_, window)| *window == needle.as_slice())
+        .any(|(start, _)| {
+            let prefix = &elf[..start];
+            if qualified {
+                // The full name must not be the suffix of a longer path.
+                !prefix
+                    .last()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_' || *c == b':')
+            } else {
+                prefix.ends_with(b"::")
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::references_syscall;
+
+    #[test]
+    fn syscall_names() {
+        let elf = b"\x7fELF\0risc0_zkvm_platform::syscall::nr::SYS_RANDOM\0SYS_READ_X\0";
+        assert!(references_syscall(elf, "SYS_RANDOM"));
+        assert!(references_syscall(
+            elf,
+            "risc0_zkvm_platform::syscall::nr::SYS_RANDOM"
+        ));
+        assert!(!references_syscall(elf, "nr::SYS_RANDOM"));
+        assert!(!references_syscall(elf, "RANDOM"));
+        assert!(!references_syscall(elf, "SYS_READ"));
+        assert!(!references_syscall(elf, "SYS_GETENV"));
+    }
+}
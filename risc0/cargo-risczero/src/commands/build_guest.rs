@@ -16,7 +16,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use clap::Parser;
-use risc0_build::{BuildStatus, GuestOptions};
+use risc0_build::{BuildStatus, GuestOptions, GuestTarget, LtoMode, PanicStrategy};
 
 /// `cargo risczero build`
 ///
@@ -32,6 +32,48 @@ pub struct BuildGuest {
     /// Feature flags passed to cargo.
     #[arg(long, value_delimiter = ',')]
     pub features: Vec<String>,
+
+    /// Value of SOURCE_DATE_EPOCH for the build, in seconds since the Unix epoch.
+    ///
+    /// Defaults to the time of the latest git commit, if available.
+    #[arg(long)]
+    pub source_date_epoch: Option<u64>,
+
+    /// Pass RUSTFLAGS from the environment on to the guest build.
+    ///
+    /// By default, host rustc flags are not applied to guests.
+    #[arg(long)]
+    pub inherit_rustflags: bool,
+
+    /// The zkVM variant to build the guest for: `rv32` or `rv64`.
+    ///
+    /// Only `rv32` is currently supported.
+    #[arg(long, default_value = "rv32")]
+    pub guest_target: GuestTarget,
+
+    /// Link-time optimization mode for the guest: `off`, `thin-local`, `thin` or `fat`.
+    ///
+    /// Defaults to the setting of the guest's release profile. Changes the image ID.
+    #[arg(long)]
+    pub guest_lto: Option<LtoMode>,
+
+    /// Number of codegen units for the guest build.
+    ///
+    /// Defaults to the setting of the guest's release profile. Changes the image ID.
+    #[arg(long)]
+    pub guest_codegen_units: Option<u32>,
+
+    /// How the guest handles panics: `abort` or `unwind`.
+    ///
+    /// Only `abort` is currently supported.
+    #[arg(long, default_value = "abort")]
+    pub guest_panic: PanicStrategy,
+
+    /// Fail the build if the guest invokes this syscall, e.g. `SYS_RANDOM`.
+    ///
+    /// May be given multiple times. The guest ELF is scanned for the syscall after building.
+    #[arg(long = "forbid-syscall", value_name = "NAME")]
+    pub forbidden_syscalls: Vec<String>,
 }
 
 impl BuildGuest {
@@ -40,6 +82,13 @@ impl BuildGuest {
             &self.manifest_path,
             &GuestOptions {
                 features: self.features.clone(),
+                source_date_epoch: self.source_date_epoch,
+                inherit_rustflags: self.inherit_rustflags,
+                target: self.guest_target,
+                lto: self.guest_lto,
+                codegen_units: self.guest_codegen_units,
+                panic_strategy: self.guest_panic,
+                forbidden_syscalls: self.forbidden_syscalls.clone(),
                 ..Default::default()
             },
         )?;
@@ -0,0 +1,233 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{io::Write, path::PathBuf};
+
+use anyhow::{bail, ensure, Context, Result};
+use clap::{Parser, ValueEnum};
+use hex::FromHex;
+use risc0_binfmt::compute_image_id;
+use risc0_zkvm::sha::{Digest, DIGEST_BYTES, DIGEST_WORDS};
+
+/// `cargo risczero convert-id`
+#[derive(Parser, Debug, Clone, PartialEq, Eq)]
+pub struct ConvertIdCommand {
+    /// The path to the ID file to convert.
+    ///
+    /// Files with an `.iid`, `.uid` or `.kid` extension hold the raw 32 bytes of the ID. Other
+    /// files may hold the raw bytes, a hex string (optionally prefixed with `0x`), a Solidity
+    /// `bytes32` literal, or a list of eight `u32` words as in the `_ID` constants generated by
+    /// `risc0-build`.
+    #[arg(required_unless_present = "elf")]
+    pub id_file: Option<PathBuf>,
+
+    /// Compute the image ID of this guest ELF.
+    ///
+    /// If an ID file is also given, the ID it holds must match the ID of the ELF.
+    #[arg(long)]
+    pub elf: Option<PathBuf>,
+
+    /// The format to emit the ID in.
+    #[arg(long, value_enum, default_value_t = IdFormat::Hex)]
+    pub format: IdFormat,
+
+    /// Write the ID to this file instead of stdout.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+/// Formats supported by `cargo risczero convert-id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[non_exhaustive]
+#[clap(rename_all = "snake_case")]
+pub enum IdFormat {
+    /// The 32 bytes of the ID.
+    Raw,
+    /// The bytes of the ID, hex encoded.
+    Hex,
+    /// The ID as eight little-endian `u32` words, as used in Rust code.
+    Words,
+    /// The ID as a Solidity `bytes32` literal.
+    Bytes32,
+}
+
+impl ConvertIdCommand {
+    pub fn run(&self) -> Result<()> {
+        let id = self.id()?;
+        let encoded = encode(id, self.format);
+        match &self.output {
+            Some(path) => std::fs::write(path, encoded)
+                .with_context(|| format!("failed to write {}", path.display()))?,
+            None => {
+                let mut stdout = std::io::stdout();
+                stdout.write_all(&encoded)?;
+                if self.format != IdFormat::Raw {
+                    writeln!(stdout)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn id(&self) -> Result<Digest> {
+        let from_file = match &self.id_file {
+            Some(path) => {
+                let contents = std::fs::read(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                let raw = path
+                    .extension()
+                    .is_some_and(|ext| ["iid", "uid", "kid"].iter().any(|x| ext == *x));
+                let id = decode(&contents, raw)
+                    .with_context(|| format!("{} is not a valid ID file", path.display()))?;
+                Some(id)
+            }
+            None => None,
+        };
+        let from_elf = match &self.elf {
+            Some(path) => {
+                let elf = std::fs::read(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                let id = compute_image_id(&elf)
+                    .with_context(|| format!("{} is not a valid zkVM ELF", path.display()))?;
+                Some(id)
+            }
+            None => None,
+        };
+
+        match (from_file, from_elf) {
+            (Some(file_id), Some(elf_id)) => {
+                ensure!(
+                    file_id == elf_id,
+                    "the ID file holds {file_id}, but the image ID of the ELF is {elf_id}"
+                );
+                Ok(file_id)
+            }
+            (Some(id), None) | (None, Some(id)) => Ok(id),
+            (None, None) => unreachable!("No ID file or ELF provided. Please provide one."),
+        }
+    }
+}
+
+/// Decode an ID from the contents of an ID file, which hold the raw bytes of the ID if `raw`
+/// is set, and are otherwise detected from their content.
+fn decode(contents: &[u8], raw: bool) -> Result<Digest> {
+    if !raw {
+        match decode_text(contents) {
+            // Raw bytes can happen to look like text.
+            Err(_) if contents.len() == DIGEST_BYTES => {}
+            result => return result,
+        }
+    }
+    ensure!(
+        contents.len() == DIGEST_BYTES,
+        "expected {DIGEST_BYTES} bytes, found {}",
+        contents.len()
+    );
+    Ok(Digest::try_from(contents)?)
+}
+
+fn decode_text(contents: &[u8]) -> Result<Digest> {
+    let text = std::str::from_utf8(contents)
+        .context("expected a hex string or a list of words")?
+        .trim();
+    if let Some(list) = text.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+        let words = list
+            .split(',')
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let word = word
+                    .strip_suffix("u32")
+                    .unwrap_or(word)
+                    .trim_end_matches('_');
+                match word.strip_prefix("0x") {
+                    Some(hex) => u32::from_str_radix(hex, 16),
+                    None => word.parse(),
+                }
+                .with_context(|| format!("invalid word: {word}"))
+            })
+            .collect::<Result<Vec<u32>>>()?;
+        let Ok(words) = <[u32; DIGEST_WORDS]>::try_from(words.as_slice()) else {
+            bail!("expected {DIGEST_WORDS} words, found {}", words.len());
+        };
+        return Ok(words.into());
+    }
+    let text = text
+        .strip_prefix("bytes32(")
+        .and_then(|x| x.strip_suffix(')'))
+        .unwrap_or(text);
+    let hex = text.strip_prefix("0x").unwrap_or(text);
+    Digest::from_hex(hex).context("expected a hex string or a list of words")
+}
+
+fn encode(id: Digest, format: IdFormat) -> Vec<u8> {
+    match format {
+        IdFormat::Raw => id.as_bytes().to_vec(),
+        IdFormat::Hex => id.to_string().into_bytes(),
+        IdFormat::Words => format!("{:?}", id.as_words()).into_bytes(),
+        IdFormat::Bytes32 => format!("bytes32(0x{id})").into_bytes(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risc0_zkvm::sha::Digest;
+
+    use super::{decode, encode, IdFormat};
+
+    #[test]
+    fn round_trip() {
+        let id = Digest::from([1, 2, 3, 4, 5, 6, 7, 0xdeadbeef]);
+        for format in [
+            IdFormat::Raw,
+            IdFormat::Hex,
+            IdFormat::Words,
+            IdFormat::Bytes32,
+        ] {
+            let encoded = encode(id, format);
+            assert_eq!(decode(&encoded, false).unwrap(), id, "{format:?}");
+        }
+
+        // Raw bytes are detected even if they are valid UTF-8.
+        assert_eq!(decode(&[0; 32], false).unwrap(), Digest::ZERO);
+    }
+
+    #[test]
+    fn words_are_little_endian() {
+        let id = Digest::from([1, 0, 0, 0, 0, 0, 0, 0x01020304]);
+        let hex = String::from_utf8(encode(id, IdFormat::Hex)).unwrap();
+        assert!(hex.starts_with("01000000"), "{hex}");
+        assert!(hex.ends_with("04030201"), "{hex}");
+        assert_eq!(
+            encode(id, IdFormat::Bytes32),
+            format!("bytes32(0x{hex})").into_bytes()
+        );
+        assert_eq!(
+            encode(id, IdFormat::Words),
+            b"[1, 0, 0, 0, 0, 0, 0, 16909060]"
+        );
+        assert_eq!(decode(format!("0x{hex}\n").as_bytes(), false).unwrap(), id);
+        assert_eq!(
+            decode(b"[0x1, 0, 0, 0, 0, 0, 0, 0x01020304u32]", false).unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn invalid_ids() {
+        assert!(decode(&[0; 31], true).is_err());
+        assert!(decode(b"[1, 2, 3]", false).is_err());
+        assert!(decode(b"not an id", false).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use risc0_binfmt::{MemoryImage, Program};
+use risc0_zkvm::{sha::Digest, GUEST_MAX_MEM, PAGE_SIZE};
+
+/// `cargo risczero inspect`
+#[derive(Parser, Debug, Clone, PartialEq, Eq)]
+pub struct InspectCommand {
+    /// The path to the guest ELF file.
+    pub elf: PathBuf,
+}
+
+/// Facts about a guest ELF, as loaded into the zkVM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ElfInfo {
+    image_id: Digest,
+    entry: u32,
+    /// Lowest and highest (exclusive) loaded addresses.
+    address_range: (u32, u32),
+    /// Number of bytes loaded from the ELF segments.
+    loaded_bytes: usize,
+    /// Number of pages in the initial memory image, including the page table.
+    pages: usize,
+}
+
+impl InspectCommand {
+    pub fn run(&self) -> Result<()> {
+        let elf = std::fs::read(&self.elf)
+            .with_context(|| format!("failed to read {}", self.elf.display()))?;
+        let info = inspect(&elf)
+            .with_context(|| format!("{} is not a valid zkVM ELF", self.elf.display()))?;
+
+        println!("image_id:      {}", info.image_id);
+        println!("entry point:   {:#010x}", info.entry);
+        println!(
+            "address range: {:#010x}..{:#010x}",
+            info.address_range.0, info.address_range.1
+        );
+        println!("loaded bytes:  {}", info.loaded_bytes);
+        println!("pages:         {} ({} bytes each)", info.pages, PAGE_SIZE);
+
+        Ok(())
+    }
+}
+
+fn inspect(elf: &[u8]) -> Result<ElfInfo> {
+    let program = Program::load_elf(elf, GUEST_MAX_MEM as u32)?;
+    let image = MemoryImage::new(&program, PAGE_SIZE as u32)?;
+
+    let word_size = std::mem::size_of::<u32>() as u32;
+    let start = program
+        .image
+        .keys()
+        .next()
+        .copied()
+        .unwrap_or(program.entry);
+    let end = program
+        .image
+        .keys()
+        .next_back()
+        .map_or(start, |addr| addr + word_size);
+    let pages = image.pages.len();
+
+    Ok(ElfInfo {
+        image_id: image.compute_id(),
+        entry: program.entry,
+        address_range: (start, end),
+        loaded_bytes: program.image.len() * word_size as usize,
+        pages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::inspect;
+
+    const LOOP_ELF: &[u8] = include_bytes!("datasheet/loop.bin");
+
+    #[test]
+    fn inspect_elf() {
+        let info = inspect(LOOP_ELF).unwrap();
+        assert_eq!(
+            info.image_id,
+            risc0_binfmt::compute_image_id(LOOP_ELF).unwrap()
+        );
+        assert!(info.address_range.0 <= info.entry && info.entry < info.address_range.1);
+        assert!(info.loaded_bytes > 0);
+    }
+
+    #[test]
+    fn inspect_malformed_elf() {
+        assert!(inspect(b"not an elf").is_err());
+    }
+}
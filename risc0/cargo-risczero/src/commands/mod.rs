@@ -17,8 +17,10 @@ pub mod build;
 
 pub mod build_guest;
 pub mod build_toolchain;
+pub mod convert_id;
 pub mod datasheet;
 pub mod deploy;
+pub mod inspect;
 pub mod install;
 pub mod new;
 pub mod verify;
@@ -29,8 +29,9 @@ use clap::{Parser, Subcommand};
 #[cfg(feature = "experimental")]
 use self::commands::build::BuildCommand;
 use self::commands::{
-    build_guest::BuildGuest, build_toolchain::BuildToolchain, datasheet::Datasheet,
-    deploy::DeployCommand, install::Install, new::NewCommand, verify::VerifyCommand,
+    build_guest::BuildGuest, build_toolchain::BuildToolchain, convert_id::ConvertIdCommand,
+    datasheet::Datasheet, deploy::DeployCommand, inspect::InspectCommand, install::Install,
+    new::NewCommand, verify::VerifyCommand,
 };
 
 #[derive(Parser)]
@@ -70,6 +71,10 @@ pub enum RisczeroCmd {
     Deploy(DeployCommand),
     /// Verifies if a receipt is valid.
     Verify(VerifyCommand),
+    /// Prints the image ID and memory layout of a guest ELF.
+    Inspect(InspectCommand),
+    /// Converts an image ID file between the raw, hex, words and Solidity formats.
+    ConvertId(ConvertIdCommand),
     /// Build a crate for RISC Zero.
     #[cfg(feature = "experimental")]
     BuildCrate(BuildCommand),
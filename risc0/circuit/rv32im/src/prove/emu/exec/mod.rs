@@ -32,7 +32,7 @@ use risc0_zkp::{
 };
 use risc0_zkvm_platform::{
     align_up,
-    memory::{is_guest_memory, GUEST_MAX_MEM},
+    memory::{is_guest_memory, GUEST_MAX_MEM, GUEST_MIN_MEM, STACK_TOP},
     syscall::{bigint, ecall, halt, reg_abi::*, IO_CHUNK_WORDS},
     PAGE_SIZE, WORD_SIZE,
 };
@@ -42,7 +42,7 @@ use super::{
     addr::{ByteAddr, WordAddr},
     bibc,
     pager::PagedMemory,
-    rv32im::{DecodedInstruction, EmuContext, Emulator, Instruction, TrapCause},
+    rv32im::{DecodedInstruction, EmuContext, Emulator, Instruction, TrapCause, TrapError},
     BIGINT2_WIDTH_BYTES, BIGINT_CYCLES, SYSTEM_START,
 };
 use crate::{
@@ -114,8 +114,30 @@ pub struct ExecutorResult {
     pub post_state: SystemState,
     pub output_digest: Option<Digest>,
     pub ecall_metrics: Vec<(String, EcallMetric)>,
+    /// Largest number of bytes below [STACK_TOP] the stack pointer reached.
+    pub max_stack_usage: u64,
 }
 
+/// The error returned when the stack guard is enabled and the guest moves its stack pointer
+/// below the bottom of the stack region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StackOverflowError {
+    pub pc: ByteAddr,
+    pub sp: u32,
+}
+
+impl std::fmt::Display for StackOverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Stack overflow: sp: 0x{:08x} is below 0x{GUEST_MIN_MEM:08x}, pc: {:?}",
+            self.sp, self.pc
+        )
+    }
+}
+
+impl std::error::Error for StackOverflowError {}
+
 #[derive(Clone, Copy, Debug, Enum)]
 enum EcallKind {
     BigInt,
@@ -172,6 +194,8 @@ pub struct Executor<'a, 'b, S: Syscall> {
     trace: Vec<Rc<RefCell<dyn TraceCallback + 'b>>>,
     cycles: SessionCycles,
     ecall_metrics: EcallMetrics,
+    min_sp: u32,
+    stack_guard: bool,
 }
 
 impl PendingState {
@@ -215,9 +239,18 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
             trace,
             cycles: SessionCycles::default(),
             ecall_metrics: Default::default(),
+            min_sp: STACK_TOP,
+            stack_guard: false,
         }
     }
 
+    /// Stop execution with a [StackOverflowError] when the guest moves its stack pointer below
+    /// the stack region.
+    pub fn with_stack_guard(mut self, enable: bool) -> Self {
+        self.stack_guard = enable;
+        self
+    }
+
     pub fn run<F: FnMut(Segment) -> Result<()>>(
         &mut self,
         segment_po2: usize,
@@ -279,6 +312,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
                     post_state,
                     syscalls: mem::take(&mut self.syscalls),
                     insn_cycles: self.insn_cycles,
+                    paging_cycles: self.pager.cycles,
                     po2: segment_po2,
                     exit_code: ExitCode::SystemSplit,
                     index: segments,
@@ -310,6 +344,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
             post_state: post_state.clone(),
             syscalls: mem::take(&mut self.syscalls),
             insn_cycles: self.insn_cycles,
+            paging_cycles: self.pager.cycles,
             po2,
             exit_code,
             index: segments,
@@ -344,6 +379,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
             post_state,
             output_digest: self.output_digest,
             ecall_metrics: ecall_metrics.into(),
+            max_stack_usage: STACK_TOP.saturating_sub(self.min_sp) as u64,
         })
     }
 
@@ -762,9 +798,9 @@ impl<'a, 'b, S: Syscall> EmuContext for Executor<'a, 'b, S> {
     }
 
     fn trap(&self, cause: TrapCause) -> Result<bool> {
-        let msg = format!("Trap: {cause:08x?}, pc: {:?}", self.pc);
-        tracing::info!("{msg}");
-        bail!("{msg}");
+        let err = TrapError { cause, pc: self.pc };
+        tracing::info!("{err}");
+        Err(err.into())
     }
 
     fn check_data_load(&self, addr: ByteAddr) -> bool {
@@ -802,6 +838,16 @@ impl<'a, 'b, S: Syscall> EmuContext for Executor<'a, 'b, S> {
     }
 
     fn store_register(&mut self, idx: usize, data: u32) -> Result<()> {
+        if idx == REG_SP {
+            self.min_sp = self.min_sp.min(data);
+            if self.stack_guard && (data as usize) < GUEST_MIN_MEM {
+                return Err(StackOverflowError {
+                    pc: self.pc,
+                    sp: data,
+                }
+                .into());
+            }
+        }
         if idx != 0 {
             // tracing::trace!("store_reg: x{idx} <= 0x{data:08x}");
             self.pager.store(SYSTEM_START + idx, data)?;
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
+
 use anyhow::Result;
 use risc0_zkvm_platform::WORD_SIZE;
 
@@ -72,19 +74,34 @@ pub struct Emulator {
     table: FastDecodeTable,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TrapCause {
     InstructionAddressMisaligned,
     InstructionAccessFault,
     IllegalInstruction(u32),
     Breakpoint,
-    LoadAddressMisaligned,
+    LoadAddressMisaligned(ByteAddr),
     LoadAccessFault(ByteAddr),
     StoreAddressMisaligned(ByteAddr),
-    StoreAccessFault,
+    StoreAccessFault(ByteAddr),
     EnvironmentCallFromUserMode,
 }
 
+/// The error returned when execution stops on a trap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrapError {
+    pub cause: TrapCause,
+    pub pc: ByteAddr,
+}
+
+impl fmt::Display for TrapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Trap: {:08x?}, pc: {:?}", self.cause, self.pc)
+    }
+}
+
+impl std::error::Error for TrapError {}
+
 #[derive(Clone, Debug, Default)]
 pub struct DecodedInstruction {
     pub insn: u32,
@@ -535,7 +552,7 @@ impl Emulator {
             }
             InsnKind::LH => {
                 if addr.0 & 0x01 != 0 {
-                    return ctx.trap(TrapCause::LoadAddressMisaligned);
+                    return ctx.trap(TrapCause::LoadAddressMisaligned(addr));
                 }
                 let mut out = (data >> shift) & 0xffff;
                 if out & 0x8000 != 0 {
@@ -545,14 +562,14 @@ impl Emulator {
             }
             InsnKind::LW => {
                 if addr.0 & 0x03 != 0 {
-                    return ctx.trap(TrapCause::LoadAddressMisaligned);
+                    return ctx.trap(TrapCause::LoadAddressMisaligned(addr));
                 }
                 data
             }
             InsnKind::LBU => (data >> shift) & 0xff,
             InsnKind::LHU => {
                 if addr.0 & 0x01 != 0 {
-                    return ctx.trap(TrapCause::LoadAddressMisaligned);
+                    return ctx.trap(TrapCause::LoadAddressMisaligned(addr));
                 }
                 (data >> shift) & 0xffff
             }
@@ -574,7 +591,7 @@ impl Emulator {
         let addr = ByteAddr(rs1.wrapping_add(decoded.imm_s()));
         let shift = 8 * (addr.0 & 3);
         if !ctx.check_data_store(addr) {
-            return ctx.trap(TrapCause::StoreAccessFault);
+            return ctx.trap(TrapCause::StoreAccessFault(addr));
         }
         let mut data = ctx.load_memory(addr.waddr())?;
         match kind {
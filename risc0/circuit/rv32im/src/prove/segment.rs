@@ -40,6 +40,7 @@ pub struct Segment {
     #[debug(skip)]
     pub syscalls: Vec<SyscallRecord>,
     pub insn_cycles: usize,
+    pub paging_cycles: usize,
     pub po2: usize,
     pub exit_code: ExitCode,
     pub index: usize,
@@ -49,6 +49,10 @@ pub enum VerificationError {
     },
     InvalidProof,
     JournalDigestMismatch,
+    JournalMismatch {
+        expected: Digest,
+        received: Digest,
+    },
     ClaimDigestMismatch {
         expected: Digest,
         received: Digest,
@@ -71,6 +75,10 @@ pub enum VerificationError {
     UnresolvedAssumption {
         digest: Digest,
     },
+    UnsupportedProtocolVersion {
+        found: Digest,
+        allowed: Vec<Digest>,
+    },
 }
 
 impl fmt::Debug for VerificationError {
@@ -95,6 +103,9 @@ impl fmt::Display for VerificationError {
             VerificationError::JournalDigestMismatch => {
                 write!(f, "journal digest mismatch detected")
             }
+            VerificationError::JournalMismatch { expected, received } => {
+                write!(f, "journal has digest {received}, but the receipt claim commits to journal digest {expected}")
+            }
             VerificationError::ClaimDigestMismatch { expected, received } => {
                 write!(f, "claim digest does not match the expected digest {received}; expected {expected}")
             }
@@ -115,6 +126,9 @@ impl fmt::Display for VerificationError {
             VerificationError::UnresolvedAssumption { digest } => {
                 write!(f, "receipt contains an unresolved assumption: {digest}")
             }
+            VerificationError::UnsupportedProtocolVersion { found, allowed } => {
+                write!(f, "receipt was produced for a version of the verifier with parameters digest {found}, which is not one of the allowed versions: {allowed:?}")
+            }
         }
     }
 }
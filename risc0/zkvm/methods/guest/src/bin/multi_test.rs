@@ -139,9 +139,31 @@ fn main() {
         MultiTestSpec::Fault => unsafe {
             asm!("sw x0, 1(x0)");
         },
+        MultiTestSpec::StackOverflow => unsafe {
+            asm!("li sp, 0x100", "sw x0, 0(sp)");
+        },
         MultiTestSpec::Halt(exit_code) => {
             env::exit(exit_code);
         }
+        MultiTestSpec::HaltWithCode(code) => {
+            env::halt_with_code(code);
+        }
+        MultiTestSpec::ProfileScope => {
+            use risc0_zkvm::guest::profile_scope;
+
+            profile_scope!("outer");
+            for i in 0..3u32 {
+                profile_scope!("inner");
+                core::hint::black_box(i);
+            }
+        }
+        MultiTestSpec::ReadExact(len) => {
+            let bytes = env::read_exact_or_fault(len as usize);
+            env::commit_slice(&bytes);
+        }
+        MultiTestSpec::Require(value) => {
+            risc0_zkvm::guest::require!(value < 10, "value too large: {value}");
+        }
         MultiTestSpec::PauseResume(exit_code) => {
             env::log("before");
             env::pause(exit_code);
@@ -238,6 +260,24 @@ fn main() {
         MultiTestSpec::Echo { bytes } => {
             env::commit_slice(&bytes);
         }
+        MultiTestSpec::CommitAt => {
+            let offsets = [
+                env::commit_at(&"header"),
+                env::commit_at(&[1u8, 2, 3]),
+                env::commit_at(&42u64),
+            ]
+            .map(|offset| offset as u32);
+            let table = env::commit_at(&offsets);
+            env::commit(&(table as u32));
+        }
+        MultiTestSpec::CommitAndHalt { bytes, exit_code } => {
+            env::commit_slice(&bytes);
+            env::exit(exit_code);
+        }
+        MultiTestSpec::HostOutput => {
+            env::commit(&"result");
+            env::commit_host_output(&"diagnostics");
+        }
         MultiTestSpec::EchoStdout { nbytes, fd } => {
             // Unaligned buffer size to exercise things a little bit.
             let mut buf = vec![0u8; nbytes as usize];
@@ -295,6 +335,29 @@ fn main() {
             let len = memory::SYSTEM.start() as usize;
             let _data = black_box(vec![0_u8; len]);
         }
+        MultiTestSpec::OpenFile { name, len, times } => {
+            let mut buf = vec![0_u8; len as usize];
+            for _ in 0..times {
+                match env::open_file(&name) {
+                    Ok(mut file) => file.read_slice(&mut buf),
+                    Err(_) => {
+                        buf = b"not found".to_vec();
+                        break;
+                    }
+                }
+            }
+            env::commit_slice(&buf);
+        }
+        MultiTestSpec::OpenFileTwice { name, len } => {
+            let mut buf = vec![0_u8; len as usize];
+            let mut first = env::open_file(&name).unwrap();
+            first.read_slice(&mut buf);
+            let mut second = env::open_file(&name).unwrap();
+            first.read_slice(&mut buf);
+            env::commit_slice(&buf);
+            second.read_slice(&mut buf);
+            env::commit_slice(&buf);
+        }
         MultiTestSpec::RsaCompat => {
             // This test comes from: https://github.com/RustCrypto/RSA/blob/master/tests/pkcs1v15.rs
             use risc0_zkvm::sha::rust_crypto::Sha256;
@@ -15,7 +15,7 @@
 // Definitions for test selection codes used by the "multi_test" test.
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 use risc0_zkvm::{declare_syscall, sha::Digest};
 use risc0_zkvm_platform::syscall::bigint;
@@ -34,6 +34,13 @@ pub enum MultiTestSpec {
         /// Busy loop until the guest has run for at least this number of cycles
         cycles: u64,
     },
+    /// Commit a few values with `env::commit_at`, followed by a table of their offsets.
+    CommitAt,
+    /// Commit the given bytes, then halt with the given exit code.
+    CommitAndHalt {
+        bytes: Vec<u8>,
+        exit_code: u8,
+    },
     DoNothing,
     DoRandom,
     Echo {
@@ -50,20 +57,42 @@ pub enum MultiTestSpec {
     EventTrace,
     Fault,
     Halt(u8),
+    HaltWithCode(u32),
+    /// Commit a value to the journal and write a different one to the host output.
+    HostOutput,
     KeccakUpdate,
     KeccakUpdate2,
     LibM,
     Oom,
+    /// Open a file added by the host `times` times and commit the first `len` bytes read after
+    /// the last open, or `b"not found"`.
+    OpenFile {
+        name: String,
+        len: u32,
+        times: u32,
+    },
+    /// Open a file added by the host, read `len` bytes, open it again, and commit the next `len`
+    /// bytes of the first reader followed by the first `len` bytes of the second.
+    OpenFileTwice {
+        name: String,
+        len: u32,
+    },
     OutOfBounds,
     OutOfBoundsEcall,
     Panic,
     PauseResume(u8),
     Profiler,
+    /// Record cycles in nested and repeated scopes with `profile_scope!`.
+    ProfileScope,
     ReadWriteMem {
         /// Tuples of (address, value). Zero means read the value and
         /// output it; nonzero means write that value.
         values: Vec<(u32, u32)>,
     },
+    /// Read exactly the given number of bytes from stdin and commit them.
+    ReadExact(u32),
+    /// Require that the given value is less than 10.
+    Require(u32),
     RsaCompat,
     RunUnconstrained {
         // True to actually call run_unconstrained, false to run the busy loop directly as a control.
@@ -85,6 +114,8 @@ pub enum MultiTestSpec {
         num_iter: u32,
     },
     ShaSingleKeccak,
+    /// Move the stack pointer below the stack region, then write to the stack.
+    StackOverflow,
     SysFork,
     SysForkFork,
     SysForkJournalPanic,
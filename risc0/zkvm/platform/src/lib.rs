@@ -42,7 +42,10 @@ pub mod fileno {
     pub const STDIN: u32 = 0;
     pub const STDOUT: u32 = 1;
     pub const STDERR: u32 = 2;
+    /// Reserved for the journal, which the executor records for the receipt.
     pub const JOURNAL: u32 = 3;
+    /// Reserved for the host output, which the executor records for `Session::host_output`.
+    pub const HOST_OUTPUT: u32 = 4;
 }
 
 /// Align address upwards.
@@ -135,6 +135,7 @@ macro_rules! declare_syscall {
 pub mod nr {
     declare_syscall!(pub SYS_ARGC);
     declare_syscall!(pub SYS_ARGV);
+    declare_syscall!(pub SYS_CLOSE_FILE);
     declare_syscall!(pub SYS_CYCLE_COUNT);
     declare_syscall!(pub SYS_EXECUTE_ZKR);
     declare_syscall!(pub SYS_EXIT);
@@ -142,8 +143,10 @@ pub mod nr {
     declare_syscall!(pub SYS_GETENV);
     declare_syscall!(pub SYS_KECCAK);
     declare_syscall!(pub SYS_LOG);
+    declare_syscall!(pub SYS_OPEN_FILE);
     declare_syscall!(pub SYS_PANIC);
     declare_syscall!(pub SYS_PIPE);
+    declare_syscall!(pub SYS_PROFILE);
     declare_syscall!(pub SYS_PROVE_KECCAK);
     declare_syscall!(pub SYS_PROVE_ZKR);
     declare_syscall!(pub SYS_RANDOM);
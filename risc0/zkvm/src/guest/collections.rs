@@ -0,0 +1,62 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hash-based collections with a deterministic iteration order.
+//!
+//! The [std::collections::HashMap] and [std::collections::HashSet] types are seeded with random
+//! keys, which in the guest are provided by the host. As a result, iterating over them visits
+//! elements in a different order on each run, and a guest that commits data in iteration order
+//! will produce a different journal each time it is executed on the same input.
+//!
+//! The [HashMap] and [HashSet] aliases in this module use [DeterministicState], which hashes with
+//! fixed keys, so iteration order only depends on the inserted elements. Prefer these, or a
+//! [BTreeMap](std::collections::BTreeMap), whenever the iteration order can affect the journal.
+//!
+//! ```
+//! use risc0_zkvm::guest::collections::HashMap;
+//!
+//! let mut map = HashMap::default();
+//! map.insert("a", 1);
+//! map.insert("b", 2);
+//! assert_eq!(map["a"], 1);
+//! ```
+//!
+//! NOTE: Because the hash keys are fixed, the prover can choose inputs that collide. This can
+//! only make the guest run for more cycles, and does not affect soundness.
+
+use std::hash::{BuildHasherDefault, DefaultHasher};
+
+/// A [BuildHasher](std::hash::BuildHasher) that uses the same fixed keys for every instance.
+pub type DeterministicState = BuildHasherDefault<DefaultHasher>;
+
+/// A [std::collections::HashMap] with a deterministic iteration order.
+pub type HashMap<K, V> = std::collections::HashMap<K, V, DeterministicState>;
+
+/// A [std::collections::HashSet] with a deterministic iteration order.
+pub type HashSet<T> = std::collections::HashSet<T, DeterministicState>;
+
+#[cfg(test)]
+mod tests {
+    use super::HashMap;
+
+    #[test]
+    fn iteration_order_is_stable() {
+        let build = || (0..64u32).map(|i| (i, i * 2)).collect::<HashMap<_, _>>();
+        let first: Vec<_> = build().into_iter().collect();
+        for _ in 0..8 {
+            let next: Vec<_> = build().into_iter().collect();
+            assert_eq!(first, next);
+        }
+    }
+}
@@ -77,16 +77,17 @@ mod write;
 
 use alloc::{
     alloc::{alloc, Layout},
+    string::String,
     vec,
 };
-use core::cell::OnceCell;
+use core::{cell::OnceCell, fmt};
 
 use anyhow::Result;
 use bytemuck::Pod;
 use risc0_zkvm_platform::{
     align_up, fileno,
     syscall::{
-        self, sys_cycle_count, sys_exit, sys_fork, sys_halt, sys_input, sys_log, sys_pause,
+        self, nr, sys_cycle_count, sys_exit, sys_fork, sys_halt, sys_input, sys_log, sys_pause,
         syscall_2, SyscallName,
     },
     WORD_SIZE,
@@ -121,6 +122,9 @@ pub mod testing {
 
 static mut HASHER: OnceCell<Sha256> = OnceCell::new();
 
+/// Number of bytes written to the journal so far.
+static mut JOURNAL_LEN: usize = 0;
+
 /// Digest of the running list of [Assumptions], generated by the [self::verify] and
 /// [self::verify_integrity] calls made by the guest.
 static mut ASSUMPTIONS_DIGEST: MaybePruned<Assumptions> = MaybePruned::Pruned(Digest::ZERO);
@@ -140,6 +144,7 @@ pub(crate) fn init() {
     unsafe {
         #[allow(static_mut_refs)]
         HASHER.set(Sha256::new()).unwrap();
+        JOURNAL_LEN = 0;
         #[allow(static_mut_refs)]
         #[cfg(feature = "unstable")]
         KECCAK2_BATCHER.set(batcher::Keccak2Batcher::new()).unwrap();
@@ -153,6 +158,9 @@ pub(crate) fn init() {
 
 /// Finalize execution
 pub(crate) fn finalize(halt: bool, user_exit: u8) {
+    #[cfg(feature = "profile-scope")]
+    super::profile::finalize();
+
     unsafe {
         #[cfg(feature = "unstable")]
         KECCAK2_BATCHER.take().unwrap().finalize();
@@ -178,11 +186,59 @@ pub(crate) fn finalize(halt: bool, user_exit: u8) {
 /// Terminate execution of the zkVM.
 ///
 /// Use an exit code of 0 to indicate success, and non-zero to indicate an error.
+///
+/// Unlike [halt_with_code], this does not check the code against [MAX_USER_EXIT_CODE]. Codes
+/// above it are reserved by the zkVM, so the host treats the journal as partial, and exiting with
+/// [REQUIRE_FAILED_EXIT_CODE] after committing a matching error frame is reported as a failed
+/// [crate::guest::require].
 pub fn exit(exit_code: u8) -> ! {
     finalize(true, exit_code);
     unreachable!();
 }
 
+/// Largest exit code a guest may pass to [halt_with_code].
+///
+/// Codes above this value are reserved for signaling by the zkVM itself, such as
+/// [REQUIRE_FAILED_EXIT_CODE].
+pub const MAX_USER_EXIT_CODE: u32 = 0xef;
+
+/// Terminate execution of the zkVM with an application-defined exit code.
+///
+/// The code is recorded in the [crate::ReceiptClaim] as [crate::ExitCode::Halted], so a verifier
+/// sees the same outcome as the host, which can read it with `Session::user_exit_code`, and can
+/// check it with [crate::Receipt::verify_with_exit_code]. Unlike the journal, this gives a typed
+/// outcome channel usable to signal e.g. "rejected" vs "accepted".
+///
+/// Aborts if `code` is greater than [MAX_USER_EXIT_CODE].
+pub fn halt_with_code(code: u32) -> ! {
+    if code > MAX_USER_EXIT_CODE {
+        crate::guest::abort(&alloc::format!(
+            "exit code {code} is reserved, user exit codes must not exceed {MAX_USER_EXIT_CODE}"
+        ));
+    }
+    exit(code as u8)
+}
+
+/// User exit code used by [crate::guest::require] when a guest precondition does not hold.
+pub const REQUIRE_FAILED_EXIT_CODE: u8 = 0xfe;
+
+/// Marker terminating the error frame committed by [crate::guest::require].
+///
+/// The frame is appended to the journal as the message bytes, followed by the message length as
+/// a little-endian `u32`, followed by this marker.
+pub const REQUIRE_FAILED_MAGIC: [u8; 4] = *b"RQF\0";
+
+/// Commit a [crate::guest::require] error frame to the journal and halt with
+/// [REQUIRE_FAILED_EXIT_CODE].
+#[doc(hidden)]
+pub fn require_failed(message: &str) -> ! {
+    let len = message.len() as u32;
+    commit_slice(message.as_bytes());
+    commit_slice(&len.to_le_bytes());
+    commit_slice(&REQUIRE_FAILED_MAGIC);
+    exit(REQUIRE_FAILED_EXIT_CODE)
+}
+
 /// Pause the execution of the zkVM.
 ///
 /// Execution may be continued at a later time.
@@ -280,6 +336,21 @@ pub fn read_slice<T: Pod>(slice: &mut [T]) {
     stdin().read_slice(slice)
 }
 
+/// Read exactly `len` bytes from the STDIN of the zkVM, aborting if fewer are available.
+///
+/// Declare the same length on the host with `ExecutorEnvBuilder::min_input_len` so that missing
+/// input is reported before execution starts rather than partway through the guest.
+pub fn read_exact_or_fault(len: usize) -> alloc::vec::Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    let nread = stdin().read_bytes_all(&mut bytes);
+    if nread != len {
+        crate::guest::abort(&alloc::format!(
+            "insufficient input: needed {len} bytes, read {nread}"
+        ));
+    }
+    bytes
+}
+
 /// Serialize the given data and write it to the STDOUT of the zkVM.
 ///
 /// This is available to the host as the private output on the prover.
@@ -346,6 +417,9 @@ pub fn write_slice<T: Pod>(slice: &[T]) {
 /// Data in the journal is included in the receipt and is available to the
 /// verifier. It is considered "public" data.
 ///
+/// The data is serialized directly into the journal as it is visited, so committing a large
+/// structure does not need a buffer holding its serialized form in guest memory.
+///
 /// # Example
 ///
 /// ```no_run
@@ -400,9 +474,62 @@ pub fn commit_slice<T: Pod>(slice: &[T]) {
     journal().write_slice(slice);
 }
 
+/// Commit the given value to the journal, returning the byte offset at which it was written.
+///
+/// This writes the same bytes as [commit]. The offset lets the host read the value on its own with
+/// [crate::Journal::value_at], without deserializing the values committed before it. A guest
+/// that commits many values can commit a table of their offsets last, so that the host can find
+/// the fields it needs.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let offsets = [env::commit_at(&"header"), env::commit_at(&42u64)].map(|x| x as u32);
+/// // Commit the offset table, followed by its own offset as the final word of the journal.
+/// let table = env::commit_at(&offsets);
+/// env::commit(&(table as u32));
+/// ```
+pub fn commit_at<T: Serialize>(data: &T) -> usize {
+    let offset = journal_len();
+    commit(data);
+    offset
+}
+
+/// Return the number of bytes committed to the journal so far.
+pub fn journal_len() -> usize {
+    unsafe { JOURNAL_LEN }
+}
+
+/// Write the given serializable object to the host output of the zkVM.
+///
+/// Unlike [commit], the data is not part of the journal and is not committed to by the receipt,
+/// so it can carry diagnostics or other auxiliary data without affecting the claim. The host can
+/// read it back with `Session::host_output`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use risc0_zkvm::guest::env;
+///
+/// env::commit(&result);
+/// env::commit_host_output(&debug_info);
+/// ```
+pub fn commit_host_output<T: Serialize>(data: &T) {
+    host_output().write(data)
+}
+
 /// Return the number of processor cycles that have occurred since the guest
 /// began.
 ///
+/// The count covers user cycles across all segments of the current session, so it can be used
+/// to checkpoint before a cycle budget is exhausted or to attribute cost to phases of a
+/// computation. Reading it is a single system call and costs a small, fixed number of cycles.
+///
+/// The value returned during execution is recorded with the segment and replayed when it is
+/// proven, so executing and proving a guest always observe the same counts.
+///
 /// WARNING: The cycle count is provided by the host and is not checked by the zkVM circuit.
 pub fn cycle_count() -> u64 {
     sys_cycle_count()
@@ -427,20 +554,108 @@ pub fn stderr() -> FdWriter<impl for<'a> Fn(&'a [u8])> {
 }
 
 /// Return a writer for the JOURNAL.
+///
+/// With the `std` feature, the writer also implements `std::io::Write`, so serializers for other
+/// formats can write to the journal directly, e.g. with `serde_json::to_writer(env::journal(),
+/// &value)`. Writing a value with [Write::write] produces the same bytes as [commit].
 pub fn journal() -> FdWriter<impl for<'a> Fn(&'a [u8])> {
     FdWriter::new(fileno::JOURNAL, |bytes| {
         #[allow(static_mut_refs)]
         unsafe {
-            HASHER.get_mut().unwrap_unchecked().update(bytes)
+            HASHER.get_mut().unwrap_unchecked().update(bytes);
+            JOURNAL_LEN += bytes.len();
         };
     })
 }
 
+/// Return a writer for the host output, which is not part of the journal.
+pub fn host_output() -> FdWriter<impl for<'a> Fn(&'a [u8])> {
+    FdWriter::new(fileno::HOST_OUTPUT, |_| {})
+}
+
 /// Return a reader for the standard input
 pub fn stdin() -> FdReader {
     FdReader::new(fileno::STDIN)
 }
 
+/// Open the file with the given name, as provided by the host with
+/// `ExecutorEnvBuilder::add_file`.
+///
+/// Files are read-only. Each call returns a new [File] positioned at the start of the file, with
+/// its own fd that is closed when the [File] is dropped, so a file may be opened any number of
+/// times. Returns [FileNotFound] if the host did not provide a file with this name.
+///
+/// WARNING: The contents of files are provided by the host and are not checked by the zkVM
+/// circuit, like any other input.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+/// use std::io::Read;
+///
+/// let mut config = Vec::new();
+/// env::open_file("config.toml")
+///     .unwrap()
+///     .read_to_end(&mut config)
+///     .unwrap();
+/// ```
+pub fn open_file(name: &str) -> Result<File, FileNotFound> {
+    let syscall::Return(fd, _) = syscall(nr::SYS_OPEN_FILE, name.as_bytes(), &mut []);
+    if fd == u32::MAX {
+        return Err(FileNotFound(name.into()));
+    }
+    Ok(File {
+        fd,
+        reader: FdReader::new(fd),
+    })
+}
+
+/// A file provided by the host, opened with [open_file].
+///
+/// The file is read with the [Read] trait, or [std::io::Read] with the `std` feature. Its fd is
+/// closed when it is dropped.
+pub struct File {
+    fd: u32,
+    reader: FdReader,
+}
+
+impl Read for File {
+    fn read<T: DeserializeOwned>(&mut self) -> T {
+        self.reader.read()
+    }
+
+    fn read_slice<T: Pod>(&mut self, buf: &mut [T]) {
+        self.reader.read_slice(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        std::io::Read::read(&mut self.reader, buf)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        syscall(nr::SYS_CLOSE_FILE, &self.fd.to_le_bytes(), &mut []);
+    }
+}
+
+/// Error returned by [open_file] when the host did not provide a file with the given name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileNotFound(pub String);
+
+impl fmt::Display for FileNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "file not found: {}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FileNotFound {}
+
 /// Read the input digest from the input commitment.
 pub fn input_digest() -> Digest {
     Digest::new([
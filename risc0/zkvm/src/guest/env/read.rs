@@ -58,7 +58,7 @@ impl FdReader {
 
     // Like read_bytes, but fills the buffer completely or until EOF occurs.
     #[must_use = "read_bytes_all can potentially return EOF; this case should be handled."]
-    fn read_bytes_all(&mut self, mut buf: &mut [u8]) -> usize {
+    pub(crate) fn read_bytes_all(&mut self, mut buf: &mut [u8]) -> usize {
         let mut tot_read = 0;
         while !buf.is_empty() {
             let nread = self.read_bytes(buf);
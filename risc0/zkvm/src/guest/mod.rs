@@ -77,7 +77,11 @@
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "std")]
+pub mod collections;
 pub mod env;
+#[cfg(feature = "profile-scope")]
+pub mod profile;
 
 #[cfg(target_os = "zkvm")]
 use core::arch::asm;
@@ -86,7 +90,12 @@ use risc0_zkvm_platform::syscall::sys_panic;
 
 pub use risc0_zkp::core::hash::sha;
 
-pub use crate::entry;
+pub use crate::{entry, profile_scope, require};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::format;
+}
 
 #[cfg(target_os = "zkvm")]
 core::arch::global_asm!(include_str!("memset.s"));
@@ -146,6 +155,68 @@ macro_rules! entry {
     };
 }
 
+/// Require that a guest precondition holds, halting execution with a descriptive error if not.
+///
+/// Unlike `assert!`, a failed requirement does not panic. Instead, the formatted message is
+/// committed to the journal as an error frame and the guest halts with
+/// [env::REQUIRE_FAILED_EXIT_CODE]. The host executor recognizes this frame and reports it as
+/// `ExecutorError::GuestRequireFailed`, keeping the message available in optimized builds.
+///
+/// # Example
+///
+/// ```ignore
+/// use risc0_zkvm::guest::{env, require};
+///
+/// let amount: u64 = env::read();
+/// require!(amount > 0, "amount must be positive, got {amount}");
+/// ```
+#[macro_export]
+macro_rules! require {
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            $crate::guest::env::require_failed(&$crate::guest::__private::format!($($arg)+))
+        }
+    };
+}
+
+/// Record the cycles spent in the enclosing scope under the given name.
+///
+/// The cycles of every scope with the same name are summed, and reported to the host when the
+/// guest halts or pauses. The host reads them with `Session::profile_report`, which only covers
+/// the scopes exited since the guest last paused. Nested scopes are each charged for the cycles
+/// spent in them, including those of their inner scopes.
+///
+/// Recording is only enabled with the `profile-scope` feature of this crate. Without it, this
+/// macro expands to nothing, so it can be left in production guests at no cost.
+///
+/// # Example
+///
+/// ```ignore
+/// use risc0_zkvm::guest::profile_scope;
+///
+/// fn parse(input: &[u8]) {
+///     profile_scope!("parse");
+///     // ...
+/// }
+/// ```
+#[cfg(feature = "profile-scope")]
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_scope_guard = $crate::guest::profile::ScopeGuard::new($name);
+    };
+}
+
+/// Record the cycles spent in the enclosing scope under the given name.
+///
+/// Recording is only enabled with the `profile-scope` feature of this crate, which is disabled,
+/// so this macro expands to nothing.
+#[cfg(not(feature = "profile-scope"))]
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {};
+}
+
 #[cfg(target_os = "zkvm")]
 #[no_mangle]
 unsafe extern "C" fn __start() -> ! {
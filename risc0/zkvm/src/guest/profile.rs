@@ -0,0 +1,81 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cycle profiling of guest scopes, reported to the host.
+//!
+//! Scopes are recorded with [crate::guest::profile_scope] and are only compiled in when the
+//! `profile-scope` feature is enabled. When the guest halts or pauses, the cycles spent in each
+//! named scope are sent to the host, where they are available from `Session::profile_report`.
+//! Recorded scopes are cleared when they are sent, so the report of a session resumed after a
+//! pause only covers the scopes exited after the pause.
+
+use alloc::vec::Vec;
+
+use risc0_zkvm_platform::syscall::nr::SYS_PROFILE;
+
+use super::env;
+
+/// Name, total cycles and number of entries of each recorded scope, in order of first entry.
+static mut SCOPES: Vec<(&'static str, u64, u64)> = Vec::new();
+
+/// Records the cycles spent between its construction and when it is dropped.
+///
+/// Use [crate::guest::profile_scope] rather than constructing this directly.
+pub struct ScopeGuard {
+    name: &'static str,
+    start: u64,
+}
+
+impl ScopeGuard {
+    /// Start recording a scope with the given name.
+    pub fn new(name: &'static str) -> Self {
+        // Reserve the slot on entry, so that nested scopes are listed after the scopes they are
+        // nested in.
+        #[allow(static_mut_refs)]
+        let scopes = unsafe { &mut SCOPES };
+        if !scopes.iter().any(|(scope, ..)| *scope == name) {
+            scopes.push((name, 0, 0));
+        }
+        Self {
+            name,
+            start: env::cycle_count(),
+        }
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let cycles = env::cycle_count() - self.start;
+        #[allow(static_mut_refs)]
+        let scopes = unsafe { &mut SCOPES };
+        match scopes.iter_mut().find(|(name, ..)| *name == self.name) {
+            Some((_, total, count)) => {
+                *total += cycles;
+                *count += 1;
+            }
+            None => scopes.push((self.name, cycles, 1)),
+        }
+    }
+}
+
+/// Send the recorded scopes to the host, if any.
+pub(crate) fn finalize() {
+    #[allow(static_mut_refs)]
+    let scopes = unsafe { core::mem::take(&mut SCOPES) };
+    if scopes.is_empty() {
+        return;
+    }
+    let words = crate::serde::to_vec(&scopes).unwrap();
+    env::syscall(SYS_PROFILE, bytemuck::cast_slice(&words), &mut []);
+}
@@ -610,6 +610,15 @@ impl Client {
             write_fds: env.posix_io.borrow().write_fds(),
             segment_limit_po2: env.segment_limit_po2,
             session_limit: env.session_limit,
+            max_read_len: env.max_read_len.map(|limit| limit as u64),
+            min_input_len: env.min_input_len.map(|len| len as u64),
+            memory_guard: env.memory_guard,
+            stack_guard: env.stack_guard,
+            files: env
+                .files
+                .iter()
+                .map(|(name, contents)| (name.clone(), contents.to_vec()))
+                .collect(),
             trace_events: (!env.trace.is_empty()).then_some(()),
             coprocessor: env.coprocessor.is_some(),
             pprof_out: env
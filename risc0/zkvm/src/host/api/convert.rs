@@ -260,6 +260,8 @@ impl TryFrom<pb::api::ProverOpts> for ProverOpts {
                 .max_segment_po2
                 .try_into()
                 .map_err(|_| malformed_err())?,
+            verify_segments: opts.verify_segments,
+            recursion_threads: (opts.recursion_threads as usize).max(1),
         })
     }
 }
@@ -272,6 +274,8 @@ impl From<ProverOpts> for pb::api::ProverOpts {
             receipt_kind: opts.receipt_kind as i32,
             control_ids: opts.control_ids.into_iter().map(Into::into).collect(),
             max_segment_po2: opts.max_segment_po2 as u64,
+            verify_segments: opts.verify_segments,
+            recursion_threads: opts.recursion_threads as u32,
         }
     }
 }
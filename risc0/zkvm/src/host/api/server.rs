@@ -802,6 +802,17 @@ fn build_env<'a>(
         env_builder.segment_limit_po2(segment_limit_po2);
     }
     env_builder.session_limit(request.session_limit);
+    if let Some(max_read_len) = request.max_read_len {
+        env_builder.max_read_len(max_read_len as usize);
+    }
+    if let Some(min_input_len) = request.min_input_len {
+        env_builder.min_input_len(min_input_len as usize);
+    }
+    env_builder.memory_guard(request.memory_guard);
+    env_builder.stack_guard(request.stack_guard);
+    for (name, contents) in request.files.iter() {
+        env_builder.add_file(name, contents.clone());
+    }
     if request.trace_events.is_some() {
         let proxy = TraceProxy::new(conn.clone());
         env_builder.trace_callback(proxy);
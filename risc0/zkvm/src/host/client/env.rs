@@ -106,6 +106,36 @@ pub trait CoprocessorCallback {
 
 pub type CoprocessorCallbackRef<'a> = Rc<RefCell<dyn CoprocessorCallback + 'a>>;
 
+/// Describes a point at which the executor ended a segment and continued in a new one.
+///
+/// See [ExecutorEnvBuilder::on_segment_split].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SegmentSplitInfo {
+    /// The index of the segment that ended.
+    pub index: u32,
+
+    /// The program counter at which the next segment starts.
+    pub pc: u32,
+
+    /// Why the segment ended.
+    pub reason: SegmentSplitReason,
+
+    /// The number of user and paging cycles used by the segment that ended, before padding.
+    pub cycles: u64,
+}
+
+/// The reason the executor ended a segment, as reported in [SegmentSplitInfo].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SegmentSplitReason {
+    /// The segment reached the cycle limit set by [ExecutorEnvBuilder::segment_limit_po2].
+    SegmentLimit,
+
+    /// The guest paused with `env::pause`, and will continue in a new segment when resumed.
+    Pause,
+}
+
 /// Container for assumptions in the executor environment.
 #[derive(Default)]
 pub(crate) struct AssumptionReceipts(pub(crate) Vec<AssumptionReceipt>);
@@ -117,9 +147,14 @@ pub(crate) struct AssumptionReceipts(pub(crate) Vec<AssumptionReceipt>);
 #[derive(Default)]
 pub struct ExecutorEnv<'a> {
     pub(crate) env_vars: HashMap<String, String>,
+    pub(crate) files: HashMap<String, Rc<[u8]>>,
     pub(crate) args: Vec<String>,
     pub(crate) segment_limit_po2: Option<u32>,
     pub(crate) session_limit: Option<u64>,
+    pub(crate) max_read_len: Option<usize>,
+    pub(crate) min_input_len: Option<usize>,
+    pub(crate) memory_guard: bool,
+    pub(crate) stack_guard: bool,
     pub(crate) posix_io: Rc<RefCell<PosixIo<'a>>>,
     pub(crate) slice_io: Rc<RefCell<SliceIoTable<'a>>>,
     pub(crate) input: Vec<u8>,
@@ -129,6 +164,7 @@ pub struct ExecutorEnv<'a> {
     pub(crate) pprof_out: Option<PathBuf>,
     pub(crate) input_digest: Option<Digest>,
     pub(crate) coprocessor: Option<CoprocessorCallbackRef<'a>>,
+    pub(crate) on_segment_split: Option<Rc<RefCell<dyn FnMut(SegmentSplitInfo) + 'a>>>,
 }
 
 impl<'a> ExecutorEnv<'a> {
@@ -162,6 +198,19 @@ impl<'a> ExecutorEnvBuilder<'a> {
     pub fn build(&mut self) -> Result<ExecutorEnv<'a>> {
         let mut inner = mem::take(&mut self.inner);
 
+        if inner
+            .posix_io
+            .borrow()
+            .write_fds
+            .contains_key(&fileno::HOST_OUTPUT)
+        {
+            bail!(
+                "file descriptor {} is reserved for the host output, which is available from \
+                 Session::host_output",
+                fileno::HOST_OUTPUT
+            );
+        }
+
         if !inner.input.is_empty() {
             let reader = Cursor::new(inner.input.clone());
             inner
@@ -223,6 +272,95 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self
     }
 
+    /// Set the maximum number of bytes the guest may request in a single read.
+    ///
+    /// A read larger than `limit` fails execution with `ExecutorError::ReadTooLarge` instead of
+    /// being serviced. By default, reads are unbounded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .max_read_len(1024 * 1024) // 1 MiB
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn max_read_len(&mut self, limit: usize) -> &mut Self {
+        self.inner.max_read_len = Some(limit);
+        self
+    }
+
+    /// Declare the minimum number of input bytes the guest needs to run.
+    ///
+    /// Execution fails with `ExecutorError::InsufficientInput` before the guest starts if less
+    /// input was provided with [Self::write], [Self::write_slice] or [Self::write_frame]. Input
+    /// provided through a reader set with [Self::stdin] cannot be measured up front and is not
+    /// checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .write_slice(&[0u8; 32])
+    ///     .min_input_len(32)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn min_input_len(&mut self, len: usize) -> &mut Self {
+        self.inner.min_input_len = Some(len);
+        self
+    }
+
+    /// Report faulting guest memory accesses as `ExecutorError::MemoryFault`.
+    ///
+    /// When enabled, an out of bounds or misaligned load or store by the guest stops execution
+    /// with an error that includes the faulting address, the guest pc and the kind of access.
+    /// This is intended for debugging a crashing guest and is disabled by default.
+    ///
+    /// Enabling this does not change the image ID or the proof; it only affects the diagnostics
+    /// reported on the host.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .memory_guard(true)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn memory_guard(&mut self, enable: bool) -> &mut Self {
+        self.inner.memory_guard = enable;
+        self
+    }
+
+    /// Report a guest stack overflow as `ExecutorError::StackOverflow`.
+    ///
+    /// When enabled, execution stops as soon as the guest moves its stack pointer below the
+    /// bottom of the stack region, instead of faulting later on an out of bounds access. This is
+    /// disabled by default, and like [ExecutorEnvBuilder::memory_guard] does not change the image
+    /// ID or the proof. See `Session::max_stack_usage` for sizing the stack of a guest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .stack_guard(true)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn stack_guard(&mut self, enable: bool) -> &mut Self {
+        self.inner.stack_guard = enable;
+        self
+    }
+
     /// Add environment variables to the guest environment.
     ///
     /// # Example
@@ -280,6 +418,27 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self
     }
 
+    /// Add a read-only file that the guest can open by name with `env::open_file`.
+    ///
+    /// Adding a file with the same name as an existing one replaces it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .add_file("config.toml", b"threshold = 3".to_vec())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn add_file(&mut self, name: &str, contents: impl Into<Vec<u8>>) -> &mut Self {
+        self.inner
+            .files
+            .insert(name.to_string(), contents.into().into());
+        self
+    }
+
     /// Write input data to the zkVM guest stdin.
     ///
     /// This function will serialize `data` using a zkVM-optimized codec that
@@ -372,6 +531,9 @@ impl<'a> ExecutorEnvBuilder<'a> {
     }
 
     /// Add a posix-style file descriptor for writing.
+    ///
+    /// [fileno::HOST_OUTPUT] is reserved, and [ExecutorEnvBuilder::build] fails if a writer is
+    /// added for it.
     pub fn write_fd(&mut self, fd: u32, writer: impl Write + 'a) -> &mut Self {
         self.inner.posix_io.borrow_mut().with_write_fd(fd, writer);
         self
@@ -443,6 +605,28 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self
     }
 
+    /// Add a callback that is invoked whenever the executor ends a segment and continues in a new
+    /// one, e.g. to find out why a guest produces more segments than expected.
+    ///
+    /// The callback is not invoked for the last segment of an execution that halts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::{ExecutorEnv, SegmentSplitInfo};
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .on_segment_split(|info: SegmentSplitInfo| {
+    ///         println!("segment {} ended: {:?}", info.index, info.reason);
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn on_segment_split(&mut self, callback: impl FnMut(SegmentSplitInfo) + 'a) -> &mut Self {
+        self.inner.on_segment_split = Some(Rc::new(RefCell::new(callback)));
+        self
+    }
+
     /// Add a callback for coprocessor requests.
     #[stability::unstable]
     pub fn coprocessor_callback(&mut self, callback: impl CoprocessorCallback + 'a) -> &mut Self {
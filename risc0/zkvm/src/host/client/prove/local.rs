@@ -12,18 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::{path::Path, sync::Arc};
+
 use anyhow::Result;
 
 use super::{Executor, Prover, ProverOpts};
 use crate::{
-    get_prover_server, host::server::session::NullSegmentRef, ExecutorEnv, ExecutorImpl, ProveInfo,
-    Receipt, SegmentInfo, SessionInfo, VerifierContext,
+    get_prover_server,
+    host::server::{prove::with_setup, session::NullSegmentRef},
+    ExecutorEnv, ExecutorImpl, ProveInfo, ProverSetup, Receipt, SegmentInfo, SessionInfo,
+    VerifierContext,
 };
 
 /// A [Prover] implementation that selects a [ProverServer][crate::ProverServer] by calling
 /// [get_prover_server].
 pub struct LocalProver {
     name: String,
+    setup: Option<Arc<ProverSetup>>,
 }
 
 impl LocalProver {
@@ -31,8 +36,22 @@ impl LocalProver {
     pub fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
+            setup: None,
         }
     }
+
+    /// Return [LocalProver] after loading the [ProverSetup] cached at `path`.
+    ///
+    /// The setup is only used by this prover, and takes precedence over one installed with
+    /// [ProverSetup::install]. An error is returned if it cannot be read, or if it was generated
+    /// for different circuits than the ones in this build, in which case it should be
+    /// regenerated.
+    pub fn with_cached_setup(self, path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            setup: Some(Arc::new(ProverSetup::load(path)?)),
+            ..self
+        })
+    }
 }
 
 impl Prover for LocalProver {
@@ -43,7 +62,9 @@ impl Prover for LocalProver {
         elf: &[u8],
         opts: &ProverOpts,
     ) -> Result<ProveInfo> {
-        get_prover_server(opts)?.prove_with_ctx(env, ctx, elf)
+        with_setup(self.setup.clone(), || {
+            get_prover_server(opts)?.prove_with_ctx(env, ctx, elf)
+        })
     }
 
     fn get_name(&self) -> String {
@@ -51,7 +72,9 @@ impl Prover for LocalProver {
     }
 
     fn compress(&self, opts: &ProverOpts, receipt: &Receipt) -> Result<Receipt> {
-        get_prover_server(opts)?.compress(opts, receipt)
+        with_setup(self.setup.clone(), || {
+            get_prover_server(opts)?.compress(opts, receipt)
+        })
     }
 }
 
@@ -178,6 +178,20 @@ pub struct ProverOpts {
 
     /// Maximum cycle count, as a power of two (po2) that these prover options support.
     pub(crate) max_segment_po2: usize,
+
+    /// When true, each [SegmentReceipt][crate::SegmentReceipt] is verified as soon as it is
+    /// proven, and proving fails on the first segment that does not verify.
+    ///
+    /// This catches a faulty prover before its output is composed or compressed, at the cost of
+    /// the extra verification time per segment. Defaults to false.
+    pub verify_segments: bool,
+
+    /// Maximum number of recursion join operations to run concurrently when compressing a
+    /// composite receipt.
+    ///
+    /// Values greater than one join segments in a balanced tree instead of one after the other,
+    /// with up to this many joins running at each level of the tree. Defaults to 1.
+    pub recursion_threads: usize,
 }
 
 /// An enumeration of receipt kinds that can be requested to be generated.
@@ -215,6 +229,8 @@ impl Default for ProverOpts {
             receipt_kind: ReceiptKind::Composite,
             control_ids: ALLOWED_CONTROL_IDS.to_vec(),
             max_segment_po2: DEFAULT_MAX_PO2,
+            verify_segments: false,
+            recursion_threads: 1,
         }
     }
 }
@@ -236,6 +252,8 @@ impl ProverOpts {
                 .unwrap()
                 .collect(),
             max_segment_po2: po2_max,
+            verify_segments: false,
+            recursion_threads: 1,
         }
     }
 
@@ -255,6 +273,8 @@ impl ProverOpts {
             receipt_kind: ReceiptKind::Composite,
             control_ids: risc0_circuit_rv32im::control_ids("sha-256", DEFAULT_MAX_PO2).collect(),
             max_segment_po2: DEFAULT_MAX_PO2,
+            verify_segments: false,
+            recursion_threads: 1,
         }
     }
 
@@ -267,6 +287,8 @@ impl ProverOpts {
             receipt_kind: ReceiptKind::Composite,
             control_ids: ALLOWED_CONTROL_IDS.to_vec(),
             max_segment_po2: DEFAULT_MAX_PO2,
+            verify_segments: false,
+            recursion_threads: 1,
         }
     }
 
@@ -279,6 +301,8 @@ impl ProverOpts {
             receipt_kind: ReceiptKind::Succinct,
             control_ids: ALLOWED_CONTROL_IDS.to_vec(),
             max_segment_po2: DEFAULT_MAX_PO2,
+            verify_segments: false,
+            recursion_threads: 1,
         }
     }
 
@@ -293,6 +317,8 @@ impl ProverOpts {
             receipt_kind: ReceiptKind::Groth16,
             control_ids: ALLOWED_CONTROL_IDS.to_vec(),
             max_segment_po2: DEFAULT_MAX_PO2,
+            verify_segments: false,
+            recursion_threads: 1,
         }
     }
 
@@ -328,6 +354,22 @@ impl ProverOpts {
         }
     }
 
+    /// Return [ProverOpts] with verify_segments set to the given value.
+    pub fn with_verify_segments(self, verify_segments: bool) -> Self {
+        Self {
+            verify_segments,
+            ..self
+        }
+    }
+
+    /// Return [ProverOpts] with recursion_threads set to the given value.
+    pub fn with_recursion_threads(self, recursion_threads: usize) -> Self {
+        Self {
+            recursion_threads,
+            ..self
+        }
+    }
+
     /// Return [ProverOpts] with the max_segment_po2 set to the given value.
     #[stability::unstable]
     pub fn with_segment_po2_max(self, max_segment_po2: usize) -> Self {
@@ -0,0 +1,200 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use elf::{endian::LittleEndian, file::Class, ElfBytes, ParseError};
+use risc0_binfmt::{MemoryImage, Program};
+use risc0_zkp::core::digest::Digest;
+use risc0_zkvm_platform::{memory::GUEST_MAX_MEM, PAGE_SIZE, WORD_SIZE};
+
+/// Maximum number of program headers accepted in a guest ELF.
+const MAX_PROGRAM_HEADERS: usize = 256;
+
+/// Facts about a guest ELF that passed [validate_guest_elf].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GuestElfInfo {
+    /// Image ID of the guest, as computed by [crate::compute_image_id].
+    pub image_id: Digest,
+
+    /// Address of the entry point.
+    pub entry: u32,
+
+    /// Total number of bytes of memory occupied by the loadable segments.
+    pub memory_size: u64,
+}
+
+/// Reasons an ELF is rejected by [validate_guest_elf].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ElfValidationError {
+    /// The input does not start with the ELF magic bytes.
+    BadMagic,
+
+    /// The ELF is not a 32-bit ELF.
+    NotElf32,
+
+    /// The ELF targets a machine other than RISC-V.
+    WrongMachine(u16),
+
+    /// The ELF is not an executable, e.g. it is a shared or relocatable object.
+    NotExecutable,
+
+    /// The entry point is unaligned or outside of guest memory.
+    InvalidEntryPoint(u64),
+
+    /// The ELF has more program headers than the zkVM accepts.
+    TooManySegments(usize),
+
+    /// A loadable segment is unaligned or does not fit within guest memory.
+    SegmentOutOfBounds {
+        /// Virtual address of the segment.
+        vaddr: u64,
+        /// Size of the segment in memory.
+        mem_size: u64,
+    },
+
+    /// The ELF is malformed in some other way.
+    Malformed(String),
+}
+
+impl fmt::Display for ElfValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not an ELF file"),
+            Self::NotElf32 => write!(f, "not a 32-bit ELF"),
+            Self::WrongMachine(machine) => {
+                write!(f, "invalid machine type {machine}, must be RISC-V")
+            }
+            Self::NotExecutable => write!(f, "invalid ELF type, must be executable"),
+            Self::InvalidEntryPoint(entry) => write!(f, "invalid entry point {entry:#010x}"),
+            Self::TooManySegments(count) => write!(
+                f,
+                "too many program headers: {count}, at most {MAX_PROGRAM_HEADERS} are allowed"
+            ),
+            Self::SegmentOutOfBounds { vaddr, mem_size } => write!(
+                f,
+                "segment at {vaddr:#010x} of {mem_size} bytes does not fit in guest memory \
+                 (limit {GUEST_MAX_MEM:#010x})"
+            ),
+            Self::Malformed(reason) => write!(f, "malformed ELF: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ElfValidationError {}
+
+/// Check that `elf` can be loaded as a zkVM guest, and report basic facts about it.
+///
+/// This performs the same checks as loading the ELF for execution, but reports the reason for
+/// rejecting an ELF as a specific [ElfValidationError], which makes it suitable for checking
+/// untrusted ELFs before accepting them.
+pub fn validate_guest_elf(elf: &[u8]) -> Result<GuestElfInfo, ElfValidationError> {
+    let file = ElfBytes::<LittleEndian>::minimal_parse(elf).map_err(|err| match err {
+        ParseError::BadMagic(_) => ElfValidationError::BadMagic,
+        err => ElfValidationError::Malformed(err.to_string()),
+    })?;
+    if file.ehdr.class != Class::ELF32 {
+        return Err(ElfValidationError::NotElf32);
+    }
+    if file.ehdr.e_machine != elf::abi::EM_RISCV {
+        return Err(ElfValidationError::WrongMachine(file.ehdr.e_machine));
+    }
+    if file.ehdr.e_type != elf::abi::ET_EXEC {
+        return Err(ElfValidationError::NotExecutable);
+    }
+    let entry = file.ehdr.e_entry;
+    if entry >= GUEST_MAX_MEM as u64 || entry % WORD_SIZE as u64 != 0 {
+        return Err(ElfValidationError::InvalidEntryPoint(entry));
+    }
+
+    let segments = file
+        .segments()
+        .ok_or_else(|| ElfValidationError::Malformed("missing segment table".into()))?;
+    if segments.len() > MAX_PROGRAM_HEADERS {
+        return Err(ElfValidationError::TooManySegments(segments.len()));
+    }
+    let mut memory_size = 0;
+    for segment in segments.iter().filter(|x| x.p_type == elf::abi::PT_LOAD) {
+        let (vaddr, mem_size) = (segment.p_vaddr, segment.p_memsz);
+        let in_bounds = vaddr
+            .checked_add(mem_size)
+            .is_some_and(|end| end <= GUEST_MAX_MEM as u64);
+        if !in_bounds || vaddr % WORD_SIZE as u64 != 0 {
+            return Err(ElfValidationError::SegmentOutOfBounds { vaddr, mem_size });
+        }
+        if segment.p_filesz > mem_size {
+            return Err(ElfValidationError::Malformed(format!(
+                "segment at {vaddr:#010x} has a file size larger than its memory size"
+            )));
+        }
+        memory_size += mem_size;
+    }
+
+    // Anything the checks above did not catch is reported by the loader itself.
+    let program = Program::load_elf(elf, GUEST_MAX_MEM as u32)
+        .map_err(|err| ElfValidationError::Malformed(err.to_string()))?;
+    let image = MemoryImage::new(&program, PAGE_SIZE as u32)
+        .map_err(|err| ElfValidationError::Malformed(err.to_string()))?;
+
+    Ok(GuestElfInfo {
+        image_id: image.compute_id(),
+        entry: program.entry,
+        memory_size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use risc0_zkvm_methods::{MULTI_TEST_ELF, MULTI_TEST_ID};
+
+    use super::{validate_guest_elf, ElfValidationError};
+
+    #[test]
+    fn valid_guest() {
+        let info = validate_guest_elf(MULTI_TEST_ELF).unwrap();
+        assert_eq!(info.image_id, MULTI_TEST_ID.into());
+        assert!(info.memory_size > 0);
+    }
+
+    #[test]
+    fn invalid_guests() {
+        assert_eq!(
+            validate_guest_elf(b"not an elf at all").unwrap_err(),
+            ElfValidationError::BadMagic
+        );
+
+        // Flip the ELF class to 64-bit.
+        let mut elf = MULTI_TEST_ELF.to_vec();
+        elf[4] = 2;
+        assert!(validate_guest_elf(&elf).is_err());
+
+        // Change the machine type to x86-64.
+        let mut elf = MULTI_TEST_ELF.to_vec();
+        elf[18..20].copy_from_slice(&62u16.to_le_bytes());
+        assert_eq!(
+            validate_guest_elf(&elf).unwrap_err(),
+            ElfValidationError::WrongMachine(62)
+        );
+
+        // Move the entry point outside of guest memory.
+        let mut elf = MULTI_TEST_ELF.to_vec();
+        elf[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            validate_guest_elf(&elf).unwrap_err(),
+            ElfValidationError::InvalidEntryPoint(u32::MAX as u64)
+        );
+    }
+}
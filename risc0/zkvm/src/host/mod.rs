@@ -16,6 +16,8 @@
 pub(crate) mod api;
 #[cfg(feature = "client")]
 pub(crate) mod client;
+#[cfg(feature = "client")]
+mod guest_elf;
 #[cfg(any(feature = "client", feature = "prove"))]
 mod protos;
 pub(crate) mod prove_info;
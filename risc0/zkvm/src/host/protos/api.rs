@@ -393,6 +393,17 @@ pub struct ExecutorEnv {
     pub segment_path: ::prost::alloc::string::String,
     #[prost(bool, tag = "13")]
     pub coprocessor: bool,
+    #[prost(uint64, optional, tag = "14")]
+    pub max_read_len: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "15")]
+    pub min_input_len: ::core::option::Option<u64>,
+    #[prost(bool, tag = "16")]
+    pub memory_guard: bool,
+    #[prost(bool, tag = "17")]
+    pub stack_guard: bool,
+    #[prost(map = "string, bytes", tag = "18")]
+    pub files:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::vec::Vec<u8>>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -426,6 +437,10 @@ pub struct ProverOpts {
     pub control_ids: ::prost::alloc::vec::Vec<super::base::Digest>,
     #[prost(uint64, tag = "5")]
     pub max_segment_po2: u64,
+    #[prost(bool, tag = "6")]
+    pub verify_segments: bool,
+    #[prost(uint32, tag = "7")]
+    pub recursion_threads: u32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::RECURSION_PO2;
+use crate::{host::server::prove::cached_zkr, RECURSION_PO2};
 use anyhow::{anyhow, bail, Result};
 use risc0_circuit_recursion::control_id::{
     BN254_IDENTITY_CONTROL_ID, POSEIDON2_CONTROL_IDS, SHA256_CONTROL_IDS,
@@ -28,8 +28,12 @@ fn get_zkr(name: &str, hashfn: &str) -> Result<(Program, Digest)> {
         _ => bail!("no control id found for {name} with {hashfn}"),
     };
 
+    let program = match cached_zkr(name) {
+        Some(encoded) => Program::from_encoded(&encoded, RECURSION_PO2),
+        None => risc0_circuit_recursion::prove::zkr::get_zkr(name, RECURSION_PO2)?,
+    };
     Ok((
-        risc0_circuit_recursion::prove::zkr::get_zkr(name, RECURSION_PO2)?,
+        program,
         control_ids
             .iter()
             .copied()
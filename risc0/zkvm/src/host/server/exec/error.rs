@@ -0,0 +1,196 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risc0_circuit_rv32im::prove::emu::{
+    exec::StackOverflowError,
+    rv32im::{TrapCause, TrapError},
+};
+
+use crate::guest::env::REQUIRE_FAILED_MAGIC;
+
+/// Errors reported by the [crate::ExecutorImpl] for well-known guest failures.
+///
+/// These errors are returned wrapped in an [anyhow::Error] and can be recovered with
+/// [anyhow::Error::downcast_ref].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExecutorError {
+    /// The guest halted through [crate::guest::require] because a precondition did not hold.
+    GuestRequireFailed {
+        /// Message provided by the guest.
+        message: String,
+    },
+
+    /// The guest requested a single read larger than the limit set with
+    /// [crate::ExecutorEnvBuilder::max_read_len].
+    ReadTooLarge {
+        /// Number of bytes requested by the guest.
+        requested: usize,
+        /// Configured limit, in bytes.
+        limit: usize,
+    },
+
+    /// Less input was provided than declared with [crate::ExecutorEnvBuilder::min_input_len].
+    InsufficientInput {
+        /// Number of bytes declared as needed by the guest.
+        needed: usize,
+        /// Number of bytes available on stdin.
+        available: usize,
+    },
+
+    /// The guest accessed memory out of bounds or at a misaligned address.
+    ///
+    /// Only reported when [crate::ExecutorEnvBuilder::memory_guard] is enabled.
+    MemoryFault {
+        /// Address the guest tried to access.
+        addr: u32,
+        /// Program counter of the faulting instruction.
+        pc: u32,
+        /// Kind of access that faulted.
+        kind: MemoryFaultKind,
+    },
+
+    /// The guest moved its stack pointer below the bottom of the stack region.
+    ///
+    /// Only reported when [crate::ExecutorEnvBuilder::stack_guard] is enabled.
+    StackOverflow {
+        /// Program counter of the instruction that set the stack pointer.
+        pc: u32,
+        /// Value the stack pointer was set to.
+        sp: u32,
+    },
+}
+
+/// The kind of guest memory access reported by [ExecutorError::MemoryFault].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MemoryFaultKind {
+    /// A load from outside of guest memory.
+    LoadOutOfBounds,
+    /// A load from an address not aligned to the access size.
+    LoadMisaligned,
+    /// A store to outside of guest memory.
+    StoreOutOfBounds,
+    /// A store to an address not aligned to the access size.
+    StoreMisaligned,
+}
+
+impl fmt::Display for MemoryFaultKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LoadOutOfBounds => write!(f, "out of bounds load"),
+            Self::LoadMisaligned => write!(f, "misaligned load"),
+            Self::StoreOutOfBounds => write!(f, "out of bounds store"),
+            Self::StoreMisaligned => write!(f, "misaligned store"),
+        }
+    }
+}
+
+impl fmt::Display for ExecutorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GuestRequireFailed { message } => {
+                write!(f, "guest requirement failed: {message}")
+            }
+            Self::ReadTooLarge { requested, limit } => {
+                write!(
+                    f,
+                    "guest read of {requested} bytes exceeds limit of {limit} bytes"
+                )
+            }
+            Self::InsufficientInput { needed, available } => {
+                write!(
+                    f,
+                    "guest needs {needed} bytes of input, but only {available} are available"
+                )
+            }
+            Self::MemoryFault { addr, pc, kind } => {
+                write!(f, "guest {kind} at {addr:#010x}, pc: {pc:#010x}")
+            }
+            Self::StackOverflow { pc, sp } => {
+                write!(
+                    f,
+                    "guest stack overflow: sp set to {sp:#010x}, pc: {pc:#010x}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExecutorError {}
+
+/// Convert a trap on a guest memory access into [ExecutorError::MemoryFault].
+///
+/// Any other error is returned unchanged.
+pub(crate) fn memory_fault(err: anyhow::Error) -> anyhow::Error {
+    let Some(trap) = err.downcast_ref::<TrapError>() else {
+        return err;
+    };
+    let (addr, kind) = match trap.cause {
+        TrapCause::LoadAccessFault(addr) => (addr, MemoryFaultKind::LoadOutOfBounds),
+        TrapCause::LoadAddressMisaligned(addr) => (addr, MemoryFaultKind::LoadMisaligned),
+        TrapCause::StoreAccessFault(addr) => (addr, MemoryFaultKind::StoreOutOfBounds),
+        TrapCause::StoreAddressMisaligned(addr) => (addr, MemoryFaultKind::StoreMisaligned),
+        _ => return err,
+    };
+    ExecutorError::MemoryFault {
+        addr: addr.0,
+        pc: trap.pc.0,
+        kind,
+    }
+    .into()
+}
+
+/// Convert a stack overflow detected by the stack guard into [ExecutorError::StackOverflow].
+///
+/// Any other error is returned unchanged.
+pub(crate) fn stack_overflow(err: anyhow::Error) -> anyhow::Error {
+    match err.downcast_ref::<StackOverflowError>() {
+        Some(overflow) => ExecutorError::StackOverflow {
+            pc: overflow.pc.0,
+            sp: overflow.sp,
+        }
+        .into(),
+        None => err,
+    }
+}
+
+/// Decode the error frame committed by [crate::guest::env::require_failed] from the tail of the
+/// journal, if present.
+pub(crate) fn decode_require_frame(journal: &[u8]) -> Option<String> {
+    let rest = journal.strip_suffix(&REQUIRE_FAILED_MAGIC)?;
+    let (rest, len) = rest.split_at(rest.len().checked_sub(4)?);
+    let len = u32::from_le_bytes(len.try_into().ok()?) as usize;
+    let message = rest.get(rest.len().checked_sub(len)?..)?;
+    Some(String::from_utf8_lossy(message).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_require_frame;
+
+    #[test]
+    fn decode_frame() {
+        let mut journal = b"prefix".to_vec();
+        journal.extend_from_slice(b"bad input");
+        journal.extend_from_slice(&9u32.to_le_bytes());
+        journal.extend_from_slice(b"RQF\0");
+        assert_eq!(decode_require_frame(&journal).unwrap(), "bad input");
+
+        assert_eq!(decode_require_frame(b"RQF\0"), None);
+        assert_eq!(decode_require_frame(b"no frame here"), None);
+    }
+}
@@ -14,7 +14,7 @@
 
 use std::{cell::RefCell, io::Write, rc::Rc, sync::Arc, time::Instant};
 
-use anyhow::{Context as _, Result};
+use anyhow::{ensure, Context as _, Result};
 use risc0_binfmt::{MemoryImage, Program};
 use risc0_circuit_rv32im::prove::emu::{
     addr::ByteAddr,
@@ -29,11 +29,13 @@ use risc0_zkvm_platform::{fileno, memory::GUEST_MAX_MEM, PAGE_SIZE};
 use tempfile::tempdir;
 
 use crate::{
-    host::client::env::SegmentPath, Assumptions, ExecutorEnv, FileSegmentRef, Output, Segment,
-    SegmentRef, Session,
+    guest::env::REQUIRE_FAILED_EXIT_CODE, host::client::env::SegmentPath, Assumptions, ExecutorEnv,
+    ExecutorSnapshot, ExitCode, FileSegmentRef, Output, Segment, SegmentRef, SegmentSplitInfo,
+    SegmentSplitReason, SegmentStats, Session,
 };
 
 use super::{
+    error::{decode_require_frame, memory_fault, stack_overflow, ExecutorError},
     profiler::Profiler,
     syscall::{SyscallContext, SyscallTable},
 };
@@ -91,11 +93,32 @@ impl<'a> ExecutorImpl<'a> {
         Self::with_details(env, image, profiler)
     }
 
+    /// Construct a new [ExecutorImpl] that resumes execution from an [ExecutorSnapshot].
+    ///
+    /// The resulting [Session] has a `pre_state` equal to [ExecutorSnapshot::state], so its
+    /// receipt continues the one for the [Session] the snapshot was taken from.
+    pub fn from_snapshot(env: ExecutorEnv<'a>, snapshot: ExecutorSnapshot) -> Result<Self> {
+        ensure!(
+            snapshot.image.get_system_state() == snapshot.state,
+            "snapshot memory image does not match its system state"
+        );
+        Self::with_details(env, snapshot.image, None)
+    }
+
     fn with_details(
         env: ExecutorEnv<'a>,
         image: MemoryImage,
         profiler: Option<Rc<RefCell<Profiler>>>,
     ) -> Result<Self> {
+        if let Some(needed) = env.min_input_len {
+            let custom_stdin =
+                env.input.is_empty() && env.posix_io.borrow().read_fds().contains(&fileno::STDIN);
+            let available = env.input.len();
+            if !custom_stdin && available < needed {
+                return Err(ExecutorError::InsufficientInput { needed, available }.into());
+            }
+        }
+
         let syscall_table = SyscallTable::from_env(&env);
         Ok(Self {
             env,
@@ -129,19 +152,29 @@ impl<'a> ExecutorImpl<'a> {
             .posix_io
             .borrow_mut()
             .with_write_fd(fileno::JOURNAL, journal.clone());
+        let host_output = Journal::default();
+        self.env
+            .posix_io
+            .borrow_mut()
+            .with_write_fd(fileno::HOST_OUTPUT, host_output.clone());
 
         let segment_limit_po2 = self
             .env
             .segment_limit_po2
             .unwrap_or(DEFAULT_SEGMENT_LIMIT_PO2 as u32) as usize;
 
+        let memory_guard = self.env.memory_guard;
+        let stack_guard = self.env.stack_guard;
         let mut refs = Vec::new();
+        let mut segment_stats = Vec::new();
+        let on_segment_split = self.env.on_segment_split.clone();
         let mut exec = Executor::new(
             self.image.clone(),
             self,
             self.env.input_digest,
             self.env.trace.clone(),
-        );
+        )
+        .with_stack_guard(stack_guard);
 
         let start_time = Instant::now();
         let result = exec.run(segment_limit_po2, self.env.session_limit, |inner| {
@@ -172,6 +205,27 @@ impl<'a> ExecutorImpl<'a> {
                 .flatten()
                 .transpose()?;
 
+            if let Some(callback) = &on_segment_split {
+                let reason = match inner.exit_code {
+                    ExitCode::SystemSplit => Some(SegmentSplitReason::SegmentLimit),
+                    ExitCode::Paused(_) => Some(SegmentSplitReason::Pause),
+                    ExitCode::Halted(_) | ExitCode::SessionLimit => None,
+                };
+                if let Some(reason) = reason {
+                    callback.borrow_mut()(SegmentSplitInfo {
+                        index: inner.index as u32,
+                        pc: inner.post_state.pc,
+                        reason,
+                        cycles: (inner.insn_cycles + inner.paging_cycles) as u64,
+                    });
+                }
+            }
+            segment_stats.push(SegmentStats {
+                index: inner.index as u32,
+                po2: inner.po2,
+                user_cycles: inner.insn_cycles as u64,
+                paging_cycles: inner.paging_cycles as u64,
+            });
             let segment = Segment {
                 index: inner.index as u32,
                 inner,
@@ -180,19 +234,25 @@ impl<'a> ExecutorImpl<'a> {
             let segment_ref = callback(segment)?;
             refs.push(segment_ref);
             Ok(())
-        })?;
+        });
+        let result = match result.map_err(stack_overflow) {
+            Err(err) if memory_guard => return Err(memory_fault(err)),
+            result => result?,
+        };
         let elapsed = start_time.elapsed();
 
         // Set the session_journal to the committed data iff the guest set a non-zero output.
-        let session_journal = result
+        let committed = journal.buf.take();
+        let mut session_journal = result
             .output_digest
-            .and_then(|digest| (digest != Digest::ZERO).then(|| journal.buf.take()));
+            .and_then(|digest| (digest != Digest::ZERO).then(|| committed.clone()));
         if !result.exit_code.expects_output() && session_journal.is_some() {
             tracing::debug!(
                 "dropping non-empty journal due to exit code {:?}: 0x{}",
                 result.exit_code,
-                hex::encode(journal.buf.borrow().as_slice())
+                hex::encode(&committed)
             );
+            session_journal = None;
         };
 
         // Take (clear out) the list of accessed assumptions.
@@ -206,8 +266,16 @@ impl<'a> ExecutorImpl<'a> {
             std::fs::write(self.env.pprof_out.as_ref().unwrap(), report)?;
         }
 
+        if result.exit_code == ExitCode::Halted(REQUIRE_FAILED_EXIT_CODE as u32) {
+            if let Some(message) = session_journal.as_deref().and_then(decode_require_frame) {
+                return Err(ExecutorError::GuestRequireFailed { message }.into());
+            }
+        }
+
         self.image = result.post_image.clone();
         let syscall_metrics = self.syscall_table.metrics.borrow().clone();
+        let syscall_summary = self.syscall_table.summary.take();
+        let profile_report = self.syscall_table.profile_report.take();
 
         let session = Session::new(
             refs,
@@ -226,6 +294,12 @@ impl<'a> ExecutorImpl<'a> {
             pending_keccaks,
             result.ecall_metrics,
             syscall_metrics,
+            syscall_summary,
+            profile_report,
+            committed,
+            result.max_stack_usage,
+            host_output.buf.take(),
+            segment_stats,
         );
 
         tracing::info!("execution time: {elapsed:?}");
@@ -281,15 +355,16 @@ impl<'a> NewSyscall for ExecutorImpl<'a> {
             ctx,
             syscall_table: self.syscall_table.clone(),
         };
-        self.syscall_table
+        let handler = self
+            .syscall_table
             .get_syscall(syscall)
-            .context(format!("Unknown syscall: {syscall:?}"))?
-            .borrow_mut()
-            .syscall(syscall, &mut ctx, into_guest)
+            .context(format!("Unknown syscall: {syscall:?}"))?;
+        self.syscall_table.summary.borrow_mut().record(syscall);
+        handler.borrow_mut().syscall(syscall, &mut ctx, into_guest)
     }
 }
 
-// Capture the journal output in a buffer that we can access afterwards.
+// Capture the journal or host output in a buffer that we can access afterwards.
 #[derive(Clone, Default)]
 struct Journal {
     buf: Rc<RefCell<Vec<u8>>>,
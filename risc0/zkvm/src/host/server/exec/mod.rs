@@ -18,9 +18,11 @@
 //! [crate::Session] contains one or more [crate::Segment]s, each of which
 //! contains an execution trace of the specified program.
 
+pub(crate) mod error;
 pub(crate) mod executor;
 pub(crate) mod profiler;
 mod proto;
+pub(crate) mod replay;
 pub(crate) mod syscall;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,88 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, ensure, Result};
+use risc0_binfmt::compute_image_id;
+
+use crate::{sha::Digestible, ExecutorEnv, ExecutorImpl, Receipt, Session};
+
+/// Re-execute the guest that produced `receipt`, and check that the execution matches the claim
+/// of the receipt.
+///
+/// The guest is run from `elf` with `env`, which must provide the same input and assumptions as
+/// the original execution. The resulting [Session] can then be inspected, e.g. to debug an
+/// unexpected journal. The seal of the receipt is not verified.
+///
+/// Returns an error if `elf` is not the guest the receipt was produced for, or if the exit code,
+/// input, final pc or journal of the execution differ from those attested to by the receipt,
+/// which indicates that the guest is nondeterministic or that it was given different inputs.
+pub fn replay_receipt(receipt: &Receipt, elf: &[u8], env: ExecutorEnv<'_>) -> Result<Session> {
+    let claim = receipt.claim()?;
+    let claim = claim.as_value()?;
+    let image_id = compute_image_id(elf)?;
+    ensure!(
+        claim.pre.digest() == image_id,
+        "ELF has image ID {image_id}, but the receipt is for image ID {}",
+        claim.pre.digest()
+    );
+
+    let session = ExecutorImpl::from_elf(env, elf)?.run()?;
+    let replayed = session.claim()?;
+
+    // Assumptions are not compared, since they are resolved when the receipt is proven.
+    let mut divergences = Vec::new();
+    if replayed.exit_code != claim.exit_code {
+        divergences.push(format!(
+            "exit code: expected {:?}, got {:?}",
+            claim.exit_code, replayed.exit_code
+        ));
+    }
+    if replayed.input.digest() != claim.input.digest() {
+        divergences.push(format!(
+            "input: expected {}, got {}",
+            claim.input.digest(),
+            replayed.input.digest()
+        ));
+    }
+    // The Merkle root of the post state commits to memory that is randomized on every run, so
+    // only the final pc is expected to match.
+    if let (Ok(expected), Ok(replayed)) = (claim.post.as_value(), replayed.post.as_value()) {
+        if replayed.pc != expected.pc {
+            divergences.push(format!(
+                "post state pc: expected 0x{:08x}, got 0x{:08x}",
+                expected.pc, replayed.pc
+            ));
+        }
+    }
+    let journal = session
+        .journal
+        .as_ref()
+        .map(|journal| journal.bytes.as_slice())
+        .unwrap_or_default();
+    if journal != receipt.journal.bytes.as_slice() {
+        divergences.push(format!(
+            "journal: expected 0x{}, got 0x{}",
+            hex::encode(&receipt.journal.bytes),
+            hex::encode(journal)
+        ));
+    }
+    if !divergences.is_empty() {
+        bail!(
+            "replayed execution diverges from the receipt:\n  {}",
+            divergences.join("\n  ")
+        );
+    }
+
+    Ok(session)
+}
@@ -20,16 +20,22 @@ mod fork;
 mod getenv;
 mod keccak;
 mod log;
+mod open_file;
 mod panic;
 mod pipe;
 mod posix_io;
+mod profile;
 mod prove_keccak;
 mod prove_zkr;
 mod random;
 mod slice_io;
 mod verify;
 
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    rc::Rc,
+};
 
 use anyhow::{anyhow, Result};
 use enum_map::{Enum, EnumMap};
@@ -37,26 +43,31 @@ use risc0_circuit_rv32im::prove::emu::addr::ByteAddr;
 use risc0_zkp::core::digest::Digest;
 use risc0_zkvm_platform::syscall::{
     nr::{
-        SYS_ARGC, SYS_ARGV, SYS_CYCLE_COUNT, SYS_FORK, SYS_GETENV, SYS_KECCAK, SYS_LOG, SYS_PANIC,
-        SYS_PIPE, SYS_PROVE_KECCAK, SYS_PROVE_ZKR, SYS_RANDOM, SYS_READ, SYS_VERIFY_INTEGRITY,
-        SYS_WRITE,
+        SYS_ARGC, SYS_ARGV, SYS_CLOSE_FILE, SYS_CYCLE_COUNT, SYS_FORK, SYS_GETENV, SYS_KECCAK,
+        SYS_LOG, SYS_OPEN_FILE, SYS_PANIC, SYS_PIPE, SYS_PROFILE, SYS_PROVE_KECCAK, SYS_PROVE_ZKR,
+        SYS_RANDOM, SYS_READ, SYS_VERIFY_INTEGRITY, SYS_WRITE,
     },
     SyscallName, DIGEST_BYTES,
 };
 
 use crate::{
-    host::client::{
-        env::{AssumptionReceipts, CoprocessorCallbackRef, ProveKeccakRequest, ProveZkrRequest},
-        posix_io::PosixIo,
+    host::{
+        client::{
+            env::{
+                AssumptionReceipts, CoprocessorCallbackRef, ProveKeccakRequest, ProveZkrRequest,
+            },
+            posix_io::PosixIo,
+        },
+        server::session::{ProfileReport, SyscallSummary},
     },
     Assumption, AssumptionReceipt, ExecutorEnv,
 };
 
 use self::{
     args::SysArgs, cycle_count::SysCycleCount, fork::SysFork, getenv::SysGetenv, keccak::SysKeccak,
-    log::SysLog, panic::SysPanic, pipe::SysPipe, posix_io::SysRead, posix_io::SysWrite,
-    prove_keccak::SysProveKeccak, prove_zkr::SysProveZkr, random::SysRandom, slice_io::SysSliceIo,
-    verify::SysVerify,
+    log::SysLog, open_file::SysCloseFile, open_file::SysOpenFile, panic::SysPanic, pipe::SysPipe,
+    posix_io::SysRead, posix_io::SysWrite, profile::SysProfile, prove_keccak::SysProveKeccak,
+    prove_zkr::SysProveZkr, random::SysRandom, slice_io::SysSliceIo, verify::SysVerify,
 };
 
 /// A host-side implementation of a system call.
@@ -138,6 +149,9 @@ pub(crate) struct SyscallTable<'a> {
     pub(crate) pending_zkrs: Rc<RefCell<Vec<ProveZkrRequest>>>,
     pub(crate) pending_keccaks: Rc<RefCell<Vec<ProveKeccakRequest>>>,
     pub(crate) metrics: Rc<RefCell<EnumMap<SyscallKind, SyscallMetric>>>,
+    pub(crate) summary: Rc<RefCell<SyscallSummary>>,
+    pub(crate) profile_report: Rc<RefCell<Option<ProfileReport>>>,
+    pub(crate) open_files: Rc<RefCell<BTreeSet<u32>>>,
 }
 
 impl<'a> SyscallTable<'a> {
@@ -151,6 +165,9 @@ impl<'a> SyscallTable<'a> {
             pending_zkrs: Default::default(),
             pending_keccaks: Default::default(),
             metrics: Default::default(),
+            summary: Default::default(),
+            profile_report: Default::default(),
+            open_files: Default::default(),
         }
     }
 
@@ -159,17 +176,25 @@ impl<'a> SyscallTable<'a> {
 
         this.with_syscall(SYS_ARGC, SysArgs(env.args.clone()))
             .with_syscall(SYS_ARGV, SysArgs(env.args.clone()))
+            .with_syscall(SYS_CLOSE_FILE, SysCloseFile)
             .with_syscall(SYS_CYCLE_COUNT, SysCycleCount)
             .with_syscall(SYS_FORK, SysFork)
             .with_syscall(SYS_GETENV, SysGetenv(env.env_vars.clone()))
             .with_syscall(SYS_KECCAK, SysKeccak)
             .with_syscall(SYS_LOG, SysLog)
+            .with_syscall(SYS_OPEN_FILE, SysOpenFile(env.files.clone()))
             .with_syscall(SYS_PANIC, SysPanic)
             .with_syscall(SYS_PIPE, SysPipe::default())
+            .with_syscall(SYS_PROFILE, SysProfile)
             .with_syscall(SYS_PROVE_ZKR, SysProveZkr)
             .with_syscall(SYS_PROVE_KECCAK, SysProveKeccak)
             .with_syscall(SYS_RANDOM, SysRandom)
-            .with_syscall(SYS_READ, SysRead)
+            .with_syscall(
+                SYS_READ,
+                SysRead {
+                    max_read_len: env.max_read_len,
+                },
+            )
             .with_syscall(SYS_VERIFY_INTEGRITY, SysVerify)
             .with_syscall(SYS_WRITE, SysWrite);
         for (syscall, handler) in env.slice_io.borrow().inner.iter() {
@@ -0,0 +1,85 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::HashMap, io::Cursor, rc::Rc};
+
+use anyhow::{anyhow, ensure, Result};
+use risc0_circuit_rv32im::prove::emu::addr::ByteAddr;
+use risc0_zkvm_platform::syscall::reg_abi::{REG_A3, REG_A4};
+
+use super::{Syscall, SyscallContext};
+
+/// Opens the files added with `ExecutorEnvBuilder::add_file`, each time as a new read fd.
+pub(crate) struct SysOpenFile(pub HashMap<String, Rc<[u8]>>);
+
+impl Syscall for SysOpenFile {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        _to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let buf_ptr = ByteAddr(ctx.load_register(REG_A3));
+        let buf_len = ctx.load_register(REG_A4);
+        let from_guest = ctx.load_region(buf_ptr, buf_len)?;
+        let name = std::str::from_utf8(&from_guest)?;
+
+        let Some(contents) = self.0.get(name) else {
+            tracing::debug!("sys_open_file({name}): not found");
+            return Ok((u32::MAX, 0));
+        };
+        let mut posix_io = ctx.syscall_table().posix_io.borrow_mut();
+        let fd = posix_io
+            .find_free_fd(0)
+            .ok_or(anyhow!("Could not allocate a file descriptor for {name}"))?;
+        posix_io.with_read_fd(fd, Cursor::new(contents.clone()));
+        ctx.syscall_table().open_files.borrow_mut().insert(fd);
+        tracing::debug!("sys_open_file({name}) -> {fd}");
+        Ok((fd, 0))
+    }
+}
+
+/// Closes an fd opened by [SysOpenFile], so that it can be reused.
+pub(crate) struct SysCloseFile;
+
+impl Syscall for SysCloseFile {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        _to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let buf_ptr = ByteAddr(ctx.load_register(REG_A3));
+        let buf_len = ctx.load_register(REG_A4);
+        let from_guest = ctx.load_region(buf_ptr, buf_len)?;
+        let fd = u32::from_le_bytes(
+            from_guest
+                .as_slice()
+                .try_into()
+                .map_err(|_| anyhow!("sys_close_file: malformed file descriptor"))?,
+        );
+
+        ensure!(
+            ctx.syscall_table().open_files.borrow_mut().remove(&fd),
+            "sys_close_file: {fd} is not an open file"
+        );
+        ctx.syscall_table()
+            .posix_io
+            .borrow_mut()
+            .read_fds
+            .remove(&fd);
+        tracing::debug!("sys_close_file({fd})");
+        Ok((0, 0))
+    }
+}
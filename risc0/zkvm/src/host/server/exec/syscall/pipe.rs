@@ -49,7 +49,7 @@ impl Syscall for SysPipe {
 }
 
 impl<'a> PosixIo<'a> {
-    fn find_free_fd(&self, start: u32) -> Option<u32> {
+    pub(super) fn find_free_fd(&self, start: u32) -> Option<u32> {
         (start..MAX_FD)
             .find(|&i| !self.read_fds.contains_key(&i) && !self.write_fds.contains_key(&i))
     }
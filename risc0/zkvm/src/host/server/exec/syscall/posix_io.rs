@@ -19,9 +19,13 @@ use risc0_zkvm_platform::{
     WORD_SIZE,
 };
 
+use crate::ExecutorError;
+
 use super::{Syscall, SyscallContext, SyscallKind};
 
-pub(crate) struct SysRead;
+pub(crate) struct SysRead {
+    pub(crate) max_read_len: Option<usize>,
+}
 
 impl Syscall for SysRead {
     fn syscall(
@@ -38,6 +42,16 @@ impl Syscall for SysRead {
             to_guest.len() * WORD_SIZE
         );
 
+        if let Some(limit) = self.max_read_len {
+            if nbytes > limit {
+                return Err(ExecutorError::ReadTooLarge {
+                    requested: nbytes,
+                    limit,
+                }
+                .into());
+            }
+        }
+
         assert!(
             nbytes >= to_guest.len() * WORD_SIZE,
             "Word-aligned read buffer must be fully filled"
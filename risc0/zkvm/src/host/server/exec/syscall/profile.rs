@@ -0,0 +1,61 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use risc0_circuit_rv32im::prove::emu::addr::ByteAddr;
+use risc0_zkvm_platform::{
+    syscall::reg_abi::{REG_A3, REG_A4},
+    WORD_SIZE,
+};
+
+use super::{Syscall, SyscallContext};
+use crate::host::server::session::{ProfileReport, ScopeProfile};
+
+pub(crate) struct SysProfile;
+
+impl Syscall for SysProfile {
+    fn syscall(
+        &mut self,
+        _syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        _to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let buf_ptr = ByteAddr(ctx.load_register(REG_A3));
+        let buf_len = ctx.load_register(REG_A4);
+        let from_guest = ctx.load_region(buf_ptr, buf_len)?;
+        ensure!(
+            from_guest.len() % WORD_SIZE == 0,
+            "sys_profile: report is not word aligned"
+        );
+        let words: Vec<u32> = from_guest
+            .chunks_exact(WORD_SIZE)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+        let scopes: Vec<(String, u64, u64)> = crate::serde::from_slice(&words)?;
+
+        tracing::debug!("sys_profile({} scopes)", scopes.len());
+
+        *ctx.syscall_table().profile_report.borrow_mut() = Some(ProfileReport {
+            scopes: scopes
+                .into_iter()
+                .map(|(name, cycles, count)| ScopeProfile {
+                    name,
+                    cycles,
+                    count,
+                })
+                .collect(),
+        });
+        Ok((0, 0))
+    }
+}
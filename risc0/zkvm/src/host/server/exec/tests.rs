@@ -28,21 +28,29 @@ use risc0_zkvm_methods::{
     BLST_ELF, HEAP_ELF, HELLO_COMMIT_ELF, MULTI_TEST_ELF, RAND_ELF, SLICE_IO_ELF, STANDARD_LIB_ELF,
     SYS_ARGS_ELF, SYS_ENV_ELF, ZKVM_527_ELF,
 };
-use risc0_zkvm_platform::{fileno, syscall::nr::SYS_RANDOM, PAGE_SIZE, WORD_SIZE};
+use risc0_zkvm_platform::{
+    fileno,
+    memory::{GUEST_MIN_MEM, STACK_TOP},
+    syscall::nr::{SYS_GETENV, SYS_RANDOM, SYS_READ},
+    PAGE_SIZE, WORD_SIZE,
+};
 use sha2::{Digest as _, Sha256};
 use test_log::test;
 
 use crate::{
+    guest::env::MAX_USER_EXIT_CODE,
     host::server::{
         exec::{
             profiler::{Frame, Profiler},
+            replay::replay_receipt,
             syscall::{Syscall, SyscallContext},
         },
         testutils,
     },
-    serde::to_vec,
+    serde::{from_slice, to_vec},
     sha::{Digest, Digestible},
-    ExecutorEnv, ExecutorImpl, ExitCode,
+    ExecutorEnv, ExecutorError, ExecutorImpl, ExitCode, FakeReceipt, InnerReceipt, JournalStatus,
+    MemoryFaultKind, Receipt, SegmentSplitReason,
 };
 
 fn run_test(spec: MultiTestSpec) {
@@ -462,6 +470,352 @@ fn unaligned_short_read() {
     assert_eq!(actual, expected, "pos and lens: {spec:?}");
 }
 
+#[test]
+fn max_read_len() {
+    const FD: u32 = 123;
+    let readbuf = vec![b'x'; 1000];
+
+    let run = |limit: usize| {
+        let env = ExecutorEnv::builder()
+            .read_fd(FD, readbuf.as_slice())
+            .write(&MultiTestSpec::EchoStdout {
+                nbytes: readbuf.len() as u32,
+                fd: FD,
+            })
+            .unwrap()
+            .max_read_len(limit)
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap().run()
+    };
+
+    assert!(run(1000).is_ok());
+
+    let err = run(100).err().unwrap();
+    assert_eq!(
+        err.downcast_ref::<ExecutorError>().unwrap(),
+        &ExecutorError::ReadTooLarge {
+            requested: 1000,
+            limit: 100
+        }
+    );
+}
+
+#[test]
+fn halt_with_code() {
+    let run = |code: u32| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::HaltWithCode(code))
+            .unwrap()
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap().run()
+    };
+
+    let session = run(42).unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(42));
+    assert_eq!(session.user_exit_code(), 42);
+    assert_eq!(session.journal_status(), JournalStatus::Complete);
+
+    let err = run(MAX_USER_EXIT_CODE + 1).err().unwrap();
+    assert!(format!("{err:?}").contains("is reserved"), "{err:?}");
+}
+
+#[test]
+fn commit_at() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CommitAt)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    let journal = session.journal.unwrap();
+
+    // The offset of the table is the last word of the journal.
+    let table: u32 = journal.value_at(journal.bytes.len() - WORD_SIZE).unwrap();
+    let offsets: [u32; 3] = journal.value_at(table as usize).unwrap();
+    assert_eq!(offsets[0], 0);
+    assert_eq!(
+        journal.value_at::<String>(offsets[0] as usize).unwrap(),
+        "header"
+    );
+    assert_eq!(
+        journal.value_at::<[u8; 3]>(offsets[1] as usize).unwrap(),
+        [1, 2, 3]
+    );
+    assert_eq!(journal.value_at::<u64>(offsets[2] as usize).unwrap(), 42);
+    assert!(journal.value_at::<u32>(journal.bytes.len() + 1).is_err());
+    assert!(matches!(
+        journal.value_at::<u8>(journal.bytes.len() - 1),
+        Err(crate::serde::Error::DeserializeUnexpectedEnd)
+    ));
+}
+
+#[test]
+fn host_output() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::HostOutput)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+
+    let journal = session.journal().unwrap();
+    assert_eq!(journal.decode::<String>().unwrap(), "result");
+    let host_output: String = from_slice(session.host_output()).unwrap();
+    assert_eq!(host_output, "diagnostics");
+
+    // Only the journal is part of the claim.
+    let claim = session.claim().unwrap();
+    let output = claim.output.as_value().unwrap().as_ref().unwrap();
+    assert_eq!(output.journal.digest(), journal.bytes.digest());
+
+    // The host output fd cannot be taken over by a user writer.
+    let err = ExecutorEnv::builder()
+        .write_fd(fileno::HOST_OUTPUT, Vec::new())
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("is reserved"), "{err}");
+}
+
+#[test]
+fn segment_stats() {
+    let env = ExecutorEnv::builder()
+        .segment_limit_po2(16)
+        .write(&MultiTestSpec::BusyLoop { cycles: 200_000 })
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+
+    let stats = session.segment_stats();
+    assert!(stats.len() > 1);
+    assert_eq!(stats.len(), session.segments.len());
+    for (index, segment) in stats.iter().enumerate() {
+        assert_eq!(segment.index, index as u32);
+        assert!(segment.paging_cycles > 0);
+        assert!(segment.user_cycles + segment.paging_cycles <= 1 << segment.po2);
+    }
+    let user_cycles: u64 = stats.iter().map(|segment| segment.user_cycles).sum();
+    let paging_cycles: u64 = stats.iter().map(|segment| segment.paging_cycles).sum();
+    assert_eq!(user_cycles, session.user_cycles);
+    assert_eq!(paging_cycles, session.paging_cycles);
+}
+
+#[test]
+fn on_segment_split() {
+    let mut splits = Vec::new();
+    let env = ExecutorEnv::builder()
+        .segment_limit_po2(16)
+        .write(&MultiTestSpec::BusyLoop { cycles: 200_000 })
+        .unwrap()
+        .on_segment_split(|info| splits.push(info))
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+
+    // The last segment halts, so it is not a split.
+    assert_eq!(splits.len(), session.segments.len() - 1);
+    for (index, split) in splits.iter().enumerate() {
+        assert_eq!(split.index, index as u32);
+        assert_eq!(split.reason, SegmentSplitReason::SegmentLimit);
+        assert!(split.cycles < 1 << 16);
+    }
+}
+
+#[test]
+fn open_file() {
+    let run = |name: &str, len: u32, times: u32| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::OpenFile {
+                name: name.to_string(),
+                len,
+                times,
+            })
+            .unwrap()
+            .add_file("greeting.txt", b"hello, world".to_vec())
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        session.journal().unwrap().bytes.clone()
+    };
+
+    assert_eq!(run("greeting.txt", 12, 1), b"hello, world");
+    assert_eq!(run("missing.txt", 0, 1), b"not found");
+
+    // The fd of each open is closed when the file is dropped, so there is no limit on how often
+    // a file can be opened.
+    assert_eq!(run("greeting.txt", 5, 2000), b"hello");
+
+    // Each open of a file reads it independently.
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::OpenFileTwice {
+            name: "greeting.txt".to_string(),
+            len: 5,
+        })
+        .unwrap()
+        .add_file("greeting.txt", b"hello, world".to_vec())
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.journal.unwrap().bytes, b", worhello");
+}
+
+#[test]
+fn replay() {
+    let run = |bytes: &[u8]| {
+        ExecutorEnv::builder()
+            .write(&MultiTestSpec::Echo {
+                bytes: bytes.to_vec(),
+            })
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+    let session = ExecutorImpl::from_elf(run(b"hello"), MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    let receipt = Receipt::new(
+        InnerReceipt::Fake(FakeReceipt::new(session.claim().unwrap())),
+        session.journal.unwrap().bytes,
+    );
+
+    let replayed = replay_receipt(&receipt, MULTI_TEST_ELF, run(b"hello")).unwrap();
+    assert_eq!(replayed.journal.unwrap().bytes, b"hello");
+
+    // Different input produces a different journal.
+    let err = replay_receipt(&receipt, MULTI_TEST_ELF, run(b"world")).unwrap_err();
+    assert!(err.to_string().contains("journal: expected"), "{err}");
+
+    // The receipt was not produced by this ELF.
+    let err = replay_receipt(&receipt, HELLO_COMMIT_ELF, run(b"hello")).unwrap_err();
+    assert!(err.to_string().contains("but the receipt is for"), "{err}");
+}
+
+#[test]
+fn journal_status() {
+    let run = |exit_code: u8| {
+        let env = ExecutorEnv::builder()
+            .write(&MultiTestSpec::CommitAndHalt {
+                bytes: b"partial".to_vec(),
+                exit_code,
+            })
+            .unwrap()
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+    };
+
+    let session = run(0);
+    assert_eq!(session.journal_status(), JournalStatus::Complete);
+    assert_eq!(session.journal.unwrap().bytes, b"partial");
+
+    // A non-zero user exit code is still a finished execution with a complete journal.
+    let session = run(42);
+    assert_eq!(session.exit_code, ExitCode::Halted(42));
+    assert_eq!(session.journal_status(), JournalStatus::Complete);
+    assert_eq!(session.journal.unwrap().bytes, b"partial");
+
+    // The journal of an execution that exited with a reserved code is still proven, but is
+    // marked as partial.
+    let reserved = MAX_USER_EXIT_CODE as u8 + 1;
+    let session = run(reserved);
+    assert_eq!(session.exit_code, ExitCode::Halted(reserved as u32));
+    assert_eq!(session.journal_status(), JournalStatus::Partial);
+    assert_eq!(session.committed(), b"partial");
+    assert_eq!(session.journal.as_ref().unwrap().bytes, b"partial");
+}
+
+#[test]
+fn profile_scope() {
+    let run = |spec: MultiTestSpec| {
+        let env = ExecutorEnv::builder()
+            .write(&spec)
+            .unwrap()
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+    };
+
+    let session = run(MultiTestSpec::ProfileScope);
+    let report = session.profile_report().unwrap();
+    let names: Vec<_> = report.iter().map(|scope| scope.name.as_str()).collect();
+    assert_eq!(names, ["outer", "inner"]);
+
+    let inner = report.get("inner").unwrap();
+    let outer = report.get("outer").unwrap();
+    assert_eq!(inner.count, 3);
+    assert_eq!(outer.count, 1);
+    assert!(0 < inner.cycles && inner.cycles < outer.cycles);
+
+    // Guests that record no scopes do not report a profile.
+    let session = run(MultiTestSpec::DoNothing);
+    assert!(session.profile_report().is_none());
+}
+
+#[test]
+fn min_input_len() {
+    const LEN: usize = 64;
+    // The serialized spec itself takes up two words of input.
+    const NEEDED: usize = LEN + 8;
+
+    let run = |input: &[u8], min_input_len: Option<usize>| {
+        let mut builder = ExecutorEnv::builder();
+        builder
+            .write(&MultiTestSpec::ReadExact(LEN as u32))
+            .unwrap()
+            .write_slice(input);
+        if let Some(len) = min_input_len {
+            builder.min_input_len(len);
+        }
+        ExecutorImpl::from_elf(builder.build().unwrap(), MULTI_TEST_ELF)
+            .and_then(|mut exec| exec.run())
+    };
+
+    let input = vec![0x5a; LEN];
+    let session = run(&input, Some(NEEDED)).unwrap();
+    assert_eq!(session.journal.unwrap().bytes, input);
+
+    let err = run(&input[..16], Some(NEEDED)).err().unwrap();
+    assert_eq!(
+        err.downcast_ref::<ExecutorError>().unwrap(),
+        &ExecutorError::InsufficientInput {
+            needed: NEEDED,
+            available: 24
+        }
+    );
+
+    // Without the declaration, the guest faults when it runs out of input.
+    let err = run(&input[..16], None).err().unwrap();
+    assert!(format!("{err:?}").contains("insufficient input"), "{err:?}");
+}
+
 #[test]
 fn large_io_words() {
     const FD: u32 = 123;
@@ -876,6 +1230,30 @@ fn getrandom_panic() {
         .unwrap();
 }
 
+#[test]
+fn syscall_summary() {
+    let run = |spec: MultiTestSpec| {
+        let env = ExecutorEnv::builder()
+            .write(&spec)
+            .unwrap()
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+    };
+
+    let session = run(MultiTestSpec::DoNothing);
+    let summary = session.syscall_summary();
+    assert!(summary.contains(SYS_READ.as_str()));
+    assert!(!summary.contains(SYS_GETENV.as_str()));
+    let baseline = summary.count(SYS_RANDOM.as_str());
+
+    let session = run(MultiTestSpec::DoRandom);
+    assert!(session.syscall_summary().count(SYS_RANDOM.as_str()) > baseline);
+}
+
 #[test]
 #[should_panic(expected = "Guest panicked: sys_getenv is disabled")]
 fn sys_getenv_panic() {
@@ -927,6 +1305,25 @@ fn panic() {
     assert!(err.to_string().contains("MultiTestSpec::Panic invoked"));
 }
 
+#[test]
+fn require() {
+    run_test(MultiTestSpec::Require(1));
+
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::Require(42))
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+    let err = exec.run().err().unwrap();
+    assert_eq!(
+        err.downcast_ref::<ExecutorError>().unwrap(),
+        &ExecutorError::GuestRequireFailed {
+            message: "value too large: 42".into()
+        }
+    );
+}
+
 #[test]
 fn fault() {
     let env = ExecutorEnv::builder()
@@ -1065,6 +1462,61 @@ fn memory_access() {
     assert_eq!(access_memory(0x0B00_0000).unwrap(), ExitCode::Halted(0));
 }
 
+#[test]
+fn memory_guard() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::OutOfBounds)
+        .unwrap()
+        .write(&0x0C00_0000u32)
+        .unwrap()
+        .memory_guard(true)
+        .build()
+        .unwrap();
+    let err = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .err()
+        .unwrap();
+    match err.downcast_ref::<ExecutorError>().unwrap() {
+        ExecutorError::MemoryFault { addr, pc, kind } => {
+            assert_eq!(*addr, 0x0C00_0000);
+            assert_ne!(*pc, 0);
+            assert_eq!(*kind, MemoryFaultKind::StoreOutOfBounds);
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn stack_guard() {
+    let run = |spec: MultiTestSpec, stack_guard: bool| {
+        let env = ExecutorEnv::builder()
+            .write(&spec)
+            .unwrap()
+            .stack_guard(stack_guard)
+            .build()
+            .unwrap();
+        ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap().run()
+    };
+
+    let session = run(MultiTestSpec::DoNothing, true).unwrap();
+    assert!(session.max_stack_usage() > 0);
+    assert!(session.max_stack_usage() < (STACK_TOP as usize - GUEST_MIN_MEM) as u64);
+
+    let err = run(MultiTestSpec::StackOverflow, true).err().unwrap();
+    match err.downcast_ref::<ExecutorError>().unwrap() {
+        ExecutorError::StackOverflow { pc, sp } => {
+            assert_ne!(*pc, 0);
+            assert_eq!(*sp, 0x100);
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+
+    // Without the guard, the guest faults on the store instead.
+    let err = run(MultiTestSpec::StackOverflow, false).err().unwrap();
+    assert!(err.downcast_ref::<ExecutorError>().is_none(), "{err}");
+}
+
 /// The post-state digest (i.e. the Merkle root of the memory state at the end
 /// of the program) should be randomized on each execution to avoid potential
 /// leakage of private information.
@@ -1343,3 +1795,43 @@ mod docker {
         assert!(run_session(1 << 16, 15, 17).is_ok());
     }
 }
+
+#[cfg(feature = "zstd")]
+#[test]
+fn compressed_segment_roundtrip() {
+    use std::sync::Arc;
+
+    use crate::{host::client::env::SegmentPath, FileSegmentRef, SegmentRef};
+
+    let dir = SegmentPath::TempDir(Arc::new(tempfile::tempdir().unwrap()));
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::BusyLoop { cycles: 1 << 16 })
+        .unwrap()
+        .segment_limit_po2(16)
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run_with_callback(|segment| {
+            Ok(Box::new(FileSegmentRef::with_compression(
+                &segment, &dir, 0,
+            )?))
+        })
+        .unwrap();
+
+    for segment_ref in session.segments.iter() {
+        let segment = segment_ref.resolve().unwrap();
+        let uncompressed = FileSegmentRef::new(&segment, &dir).unwrap();
+        assert_eq!(
+            bincode::serialize(&uncompressed.resolve().unwrap()).unwrap(),
+            bincode::serialize(&segment).unwrap()
+        );
+
+        let index = segment.index;
+        let size = |name: String| std::fs::metadata(dir.path().join(name)).unwrap().len();
+        let raw = size(format!("{index}.bincode"));
+        let zst = size(format!("{index}.bincode.zst"));
+        tracing::info!("segment {index}: {raw} bytes uncompressed, {zst} bytes with zstd");
+        assert!(zst < raw);
+    }
+}
@@ -17,6 +17,7 @@
 mod dev_mode;
 pub(crate) mod keccak;
 mod prover_impl;
+mod setup;
 #[cfg(test)]
 mod tests;
 
@@ -26,13 +27,16 @@ use anyhow::{anyhow, bail, ensure, Result};
 use risc0_core::field::baby_bear::{BabyBear, Elem, ExtElem};
 use risc0_zkp::hal::{CircuitHal, Hal};
 
+pub use self::setup::ProverSetup;
+pub(crate) use self::setup::{cached_zkr, current_setup, with_setup};
 use self::{dev_mode::DevModeProver, prover_impl::ProverImpl};
 use crate::{
     host::prove_info::ProveInfo,
     is_dev_mode,
     receipt::{
-        CompositeReceipt, Groth16Receipt, Groth16ReceiptVerifierParameters, InnerAssumptionReceipt,
-        InnerReceipt, SegmentReceipt, SuccinctReceipt,
+        composite::segments_in_order, CompositeReceipt, Groth16Receipt,
+        Groth16ReceiptVerifierParameters, InnerAssumptionReceipt, InnerReceipt, SegmentReceipt,
+        SuccinctReceipt,
     },
     receipt_claim::Unknown,
     sha::Digestible,
@@ -81,6 +85,21 @@ pub trait ProverServer {
         b: &SuccinctReceipt<ReceiptClaim>,
     ) -> Result<SuccinctReceipt<ReceiptClaim>>;
 
+    /// Join a sequence of [SuccinctReceipt], each continuing the one before it, into a single
+    /// [SuccinctReceipt] for the whole range.
+    ///
+    /// Receipts are taken from `receipts` as they are needed, so that joining them one at a time
+    /// only holds the running result and the next receipt in memory.
+    fn join_all(
+        &self,
+        receipts: &mut dyn Iterator<Item = Result<SuccinctReceipt<ReceiptClaim>>>,
+    ) -> Result<SuccinctReceipt<ReceiptClaim>> {
+        let first = receipts
+            .next()
+            .ok_or(anyhow!("cannot join an empty list of receipts"))??;
+        receipts.try_fold(first, |left, right| self.join(&left, &right?))
+    }
+
     /// Resolve an assumption from a conditional [SuccinctReceipt] by providing a [SuccinctReceipt]
     /// proving the validity of the assumption.
     fn resolve(
@@ -108,23 +127,16 @@ pub trait ProverServer {
         receipt: &CompositeReceipt,
     ) -> Result<SuccinctReceipt<ReceiptClaim>> {
         // Compress all receipts in the top-level session into one succinct receipt for the session.
-        let continuation_receipt = receipt
-            .segments
-            .iter()
-            .try_fold(
-                None,
-                |left: Option<SuccinctReceipt<ReceiptClaim>>,
-                 right: &SegmentReceipt|
-                 -> Result<_> {
-                    Ok(Some(match left {
-                        Some(left) => self.join(&left, &self.lift(right)?)?,
-                        None => self.lift(right)?,
-                    }))
-                },
-            )?
-            .ok_or(anyhow!(
-                "malformed composite receipt has no continuation segment receipts"
-            ))?;
+        ensure!(
+            !receipt.segments.is_empty(),
+            "malformed composite receipt has no continuation segment receipts"
+        );
+        // Lift the segments in execution order, whatever order they were proven in.
+        let continuation_receipt = self.join_all(
+            &mut segments_in_order(&receipt.segments)?
+                .into_iter()
+                .map(|segment| self.lift(segment)),
+        )?;
 
         // Compress assumptions and resolve them to get the final succinct receipt.
         receipt.assumption_receipts.iter().try_fold(
@@ -234,6 +246,54 @@ impl Session {
     }
 }
 
+impl Receipt {
+    /// Convert this receipt into a receipt of the given kind, proving the same claim.
+    ///
+    /// This receipt is verified with `ctx` before it is converted, and the conversion is done
+    /// with the default [ProverServer], as in [ProverServer::compress]. Receipts can only be
+    /// compressed: a [CompositeReceipt] can be converted to a succinct or Groth16 receipt, and a
+    /// [SuccinctReceipt] to a Groth16 receipt, as long as its segments were proven with the
+    /// poseidon2 hash function. Requesting the kind this receipt already has returns a copy, and
+    /// any other conversion returns an error.
+    pub fn to_kind(&self, kind: ReceiptKind, ctx: &VerifierContext) -> Result<Receipt> {
+        // Order of the kinds by how compressed they are, along with their names.
+        let rank = |kind| match kind {
+            ReceiptKind::Composite => (0, "composite"),
+            ReceiptKind::Succinct => (1, "succinct"),
+            ReceiptKind::Groth16 => (2, "groth16"),
+        };
+        let current = match &self.inner {
+            InnerReceipt::Composite(_) => Some(ReceiptKind::Composite),
+            InnerReceipt::Succinct(_) => Some(ReceiptKind::Succinct),
+            InnerReceipt::Groth16(_) => Some(ReceiptKind::Groth16),
+            InnerReceipt::Fake(_) => None,
+        };
+        if let Some(current) = current {
+            let ((from, from_name), (to, to_name)) = (rank(current), rank(kind));
+            ensure!(
+                from <= to,
+                "cannot convert a {from_name} receipt to a {to_name} receipt"
+            );
+        }
+        if let InnerReceipt::Composite(inner) = &self.inner {
+            if kind != ReceiptKind::Composite {
+                // The recursion programs that compress segments only accept poseidon2 seals.
+                if let Some(segment) = inner.segments.iter().find(|s| s.hashfn != "poseidon2") {
+                    bail!(
+                        "cannot convert a composite receipt with {} segment receipts to a \
+                         compressed receipt: only poseidon2 segment receipts can be compressed",
+                        segment.hashfn
+                    );
+                }
+            }
+        }
+
+        self.verify_integrity_with_context(ctx)?;
+        let opts = ProverOpts::default().with_receipt_kind(kind);
+        get_prover_server(&opts)?.compress(&opts, self)
+    }
+}
+
 /// Select a [ProverServer] based on the specified [ProverOpts] and currently
 /// compiled features.
 pub fn get_prover_server(opts: &ProverOpts) -> Result<Rc<dyn ProverServer>> {
@@ -17,7 +17,7 @@ use std::collections::HashMap;
 use anyhow::{anyhow, bail, ensure, Context, Result};
 use risc0_circuit_rv32im::prove::segment_prover;
 
-use super::{keccak::prove_keccak, ProverServer};
+use super::{current_setup, keccak::prove_keccak, with_setup, ProverServer};
 use crate::{
     host::{
         client::prove::ReceiptKind,
@@ -60,7 +60,13 @@ impl ProverServer for ProverImpl {
             for hook in &session.hooks {
                 hook.on_pre_prove_segment(&segment);
             }
-            segments.push(self.prove_segment(ctx, &segment)?);
+            let receipt = self.prove_segment(ctx, &segment)?;
+            if self.opts.verify_segments {
+                receipt
+                    .verify_integrity_with_context(ctx)
+                    .with_context(|| format!("segment {} failed verification", segment.index))?;
+            }
+            segments.push(receipt);
             for hook in &session.hooks {
                 hook.on_post_prove_segment(&segment);
             }
@@ -232,6 +238,13 @@ impl ProverServer for ProverImpl {
         join(a, b)
     }
 
+    fn join_all(
+        &self,
+        receipts: &mut dyn Iterator<Item = Result<SuccinctReceipt<ReceiptClaim>>>,
+    ) -> Result<SuccinctReceipt<ReceiptClaim>> {
+        join_tree(receipts, self.opts.recursion_threads)
+    }
+
     fn resolve(
         &self,
         conditional: &SuccinctReceipt<ReceiptClaim>,
@@ -275,3 +288,51 @@ fn check_claims(
     }
     Ok(())
 }
+
+/// Join consecutive receipts into one, running up to `threads` joins at a time.
+///
+/// With one thread, receipts are taken from `receipts` one at a time and folded into the running
+/// result. With more than one thread, all receipts are collected first and joined pairwise in a
+/// balanced tree, so that the joins at each level are independent. The resulting claim is the
+/// same regardless of the shape.
+fn join_tree(
+    receipts: &mut dyn Iterator<Item = Result<SuccinctReceipt<ReceiptClaim>>>,
+    threads: usize,
+) -> Result<SuccinctReceipt<ReceiptClaim>> {
+    if threads <= 1 {
+        let first = receipts
+            .next()
+            .ok_or(anyhow!("cannot join an empty list of receipts"))??;
+        return receipts.try_fold(first, |left, right| join(&left, &right?));
+    }
+
+    let mut level = receipts.collect::<Result<Vec<_>>>()?;
+    ensure!(!level.is_empty(), "cannot join an empty list of receipts");
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        for batch in level.chunks(2 * threads) {
+            let pairs = batch.chunks_exact(2);
+            let odd = pairs.remainder().first().cloned();
+            let joined = std::thread::scope(|scope| {
+                pairs
+                    .map(|pair| {
+                        // Workers use the same prover setup as the calling thread.
+                        let setup = current_setup();
+                        scope.spawn(move || with_setup(setup, || join(&pair[0], &pair[1])))
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .map(|handle| {
+                        handle
+                            .join()
+                            .map_err(|_| anyhow!("recursion join worker panicked"))?
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })?;
+            next.extend(joined);
+            next.extend(odd);
+        }
+        level = next;
+    }
+    Ok(level.pop().unwrap())
+}
@@ -0,0 +1,179 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prover setup data that can be generated once and cached on disk.
+
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    sha::{Digest, Digestible},
+    SuccinctReceiptVerifierParameters,
+};
+
+/// The setup installed with [ProverSetup::install], if any.
+static INSTALLED: Mutex<Option<Arc<ProverSetup>>> = Mutex::new(None);
+
+thread_local! {
+    /// The setup of the prover running on this thread, which takes precedence over [INSTALLED].
+    static SCOPED: RefCell<Option<Arc<ProverSetup>>> = const { RefCell::new(None) };
+}
+
+/// The recursion programs used by the prover, ready to be loaded without decompression.
+///
+/// The prover otherwise decompresses each recursion program from the archive embedded in the
+/// binary whenever it is used. Short-lived provers can skip this by generating
+/// a setup once with [ProverSetup::generate], saving it with [ProverSetup::save], and loading it
+/// on startup with [crate::LocalProver::with_cached_setup], or for the whole process with
+/// [ProverSetup::load] and [ProverSetup::install].
+///
+/// A setup records the version of the circuits it was generated for, and is rejected when loaded
+/// by a build of the zkVM with different circuits.
+#[derive(Serialize, Deserialize)]
+pub struct ProverSetup {
+    version: Digest,
+    zkrs: BTreeMap<String, Vec<u32>>,
+}
+
+impl ProverSetup {
+    /// Generate the setup for the circuits of this build of the zkVM.
+    pub fn generate() -> Result<Self> {
+        Ok(Self {
+            version: Self::circuit_version(),
+            zkrs: risc0_circuit_recursion::zkr::get_all_zkrs()?
+                .into_iter()
+                .collect(),
+        })
+    }
+
+    /// Write this setup to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, bincode::serialize(self)?)
+            .with_context(|| format!("failed to write prover setup to {}", path.display()))
+    }
+
+    /// Read a setup from the file at `path`, checking that it matches the circuits of this build.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read(path)
+            .with_context(|| format!("failed to read prover setup from {}", path.display()))?;
+        let setup: Self = bincode::deserialize(&contents)
+            .with_context(|| format!("{} is not a valid prover setup", path.display()))?;
+        let expected = Self::circuit_version();
+        ensure!(
+            setup.version == expected,
+            "prover setup at {} is stale: it was generated for circuit version {}, expected {}",
+            path.display(),
+            setup.version,
+            expected
+        );
+        Ok(setup)
+    }
+
+    /// Install this setup for the whole process.
+    ///
+    /// The setup is process-wide state: once installed, it is used by every prover in the
+    /// process, including [crate::LocalProver]s and [crate::ProverServer]s created before this
+    /// call, and it replaces any setup installed earlier. A [crate::LocalProver] given its own
+    /// setup with [crate::LocalProver::with_cached_setup] uses that one instead.
+    pub fn install(self) {
+        *INSTALLED.lock().unwrap() = Some(Arc::new(self));
+    }
+
+    /// Identifies the circuits of this build.
+    ///
+    /// The succinct verifier parameters commit to the control ID of every recursion program, as
+    /// well as to the proof system and circuit info, so they change whenever the setup would.
+    fn circuit_version() -> Digest {
+        SuccinctReceiptVerifierParameters::default().digest()
+    }
+}
+
+/// Run `f` with `setup` as the setup of this thread, in place of the installed one.
+pub(crate) fn with_setup<T>(setup: Option<Arc<ProverSetup>>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Arc<ProverSetup>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED.with(|scoped| *scoped.borrow_mut() = self.0.take());
+        }
+    }
+
+    let previous = SCOPED.with(|scoped| scoped.replace(setup));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Returns the setup used on this thread: the one set with [with_setup], or else the installed
+/// one, if any.
+pub(crate) fn current_setup() -> Option<Arc<ProverSetup>> {
+    SCOPED
+        .with(|scoped| scoped.borrow().clone())
+        .or_else(|| INSTALLED.lock().unwrap().clone())
+}
+
+/// Returns the encoded recursion program with the given name from the current setup, if any.
+pub(crate) fn cached_zkr(name: &str) -> Option<Vec<u32>> {
+    current_setup()?.zkrs.get(name).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{cached_zkr, with_setup, ProverSetup};
+    use crate::LocalProver;
+
+    #[test]
+    fn save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("setup.bin");
+
+        let setup = ProverSetup::generate().unwrap();
+        setup.save(&path).unwrap();
+        let loaded = ProverSetup::load(&path).unwrap();
+        assert_eq!(loaded.zkrs, setup.zkrs);
+        assert!(loaded.zkrs.contains_key("join.zkr"));
+
+        // A setup generated for other circuits is rejected.
+        let stale = ProverSetup {
+            version: Default::default(),
+            ..setup
+        };
+        stale.save(&path).unwrap();
+        let err = ProverSetup::load(&path).err().unwrap();
+        assert!(err.to_string().contains("is stale"), "{err}");
+    }
+
+    #[test]
+    fn scoped_setup() {
+        let setup = Arc::new(ProverSetup::generate().unwrap());
+        assert!(cached_zkr("join.zkr").is_none());
+        assert!(with_setup(Some(setup), || cached_zkr("join.zkr").is_some()));
+        assert!(cached_zkr("join.zkr").is_none());
+
+        let err = LocalProver::new("local")
+            .with_cached_setup("/nonexistent/setup.bin")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("failed to read"), "{err}");
+    }
+}
@@ -24,7 +24,9 @@ use super::get_prover_server;
 use crate::{
     host::server::testutils,
     serde::{from_slice, to_vec},
-    ExecutorEnv, ExecutorImpl, ExitCode, ProveInfo, ProverOpts, Receipt, Session, VerifierContext,
+    sha::Digestible,
+    ExecutorEnv, ExecutorImpl, ExecutorSnapshot, ExitCode, ProveInfo, ProverOpts, Receipt,
+    ReceiptKind, Session, VerifierContext,
 };
 
 fn prove_session_fast(session: &Session) -> Receipt {
@@ -63,6 +65,23 @@ fn prove_nothing_succinct() {
         .unwrap(); // ensure that we got a succinct receipt.
 }
 
+#[test]
+fn prove_verify_segments() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::DoNothing)
+        .unwrap()
+        .build()
+        .unwrap();
+    let opts = ProverOpts::fast().with_verify_segments(true);
+    get_prover_server(&opts)
+        .unwrap()
+        .prove(env, MULTI_TEST_ELF)
+        .unwrap()
+        .receipt
+        .verify(MULTI_TEST_ID)
+        .unwrap();
+}
+
 #[test]
 fn hashfn_poseidon2() {
     prove_nothing("poseidon2").unwrap();
@@ -90,6 +109,30 @@ fn check_image_id() {
     ));
 }
 
+#[test]
+fn replay_proven_receipt() {
+    let env = |bytes: &[u8]| {
+        ExecutorEnv::builder()
+            .write(&MultiTestSpec::Echo {
+                bytes: bytes.to_vec(),
+            })
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+    let receipt = get_prover_server(&ProverOpts::fast())
+        .unwrap()
+        .prove(env(b"hello"), MULTI_TEST_ELF)
+        .unwrap()
+        .receipt;
+
+    let replayed = crate::replay_receipt(&receipt, MULTI_TEST_ELF, env(b"hello")).unwrap();
+    assert_eq!(replayed.journal.unwrap().bytes, b"hello");
+
+    let err = crate::replay_receipt(&receipt, MULTI_TEST_ELF, env(b"world")).unwrap_err();
+    assert!(err.to_string().contains("journal: expected"), "{err}");
+}
+
 #[test]
 fn sha_basics() {
     fn run_sha(msg: &str) -> String {
@@ -381,6 +424,44 @@ fn pause_resume() {
     prove_session_fast(&session);
 }
 
+#[test]
+fn pause_snapshot_resume() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::PauseResume(0))
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+
+    // Run until sys_pause and persist the snapshot.
+    let paused = exec.run().unwrap();
+    assert_eq!(paused.exit_code, ExitCode::Paused(0));
+    let snapshot = bincode::serialize(&paused.snapshot().unwrap()).unwrap();
+    let paused_receipt = prove_session_fast(&paused);
+
+    // Resume from the snapshot with a fresh executor.
+    let snapshot: ExecutorSnapshot = bincode::deserialize(&snapshot).unwrap();
+    let env = ExecutorEnv::builder().build().unwrap();
+    let session = ExecutorImpl::from_snapshot(env, snapshot)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    assert!(session.snapshot().is_err());
+    let receipt = prove_session_fast(&session);
+
+    assert_eq!(
+        paused_receipt
+            .claim()
+            .unwrap()
+            .value()
+            .unwrap()
+            .post
+            .digest(),
+        receipt.claim().unwrap().value().unwrap().pre.digest()
+    );
+}
+
 #[test]
 fn pause_exit_nonzero() {
     let user_exit_code = 1;
@@ -403,6 +484,79 @@ fn pause_exit_nonzero() {
     prove_session_fast(&session);
 }
 
+#[test]
+fn compress_with_recursion_threads() {
+    let program = testutil::simple_loop();
+    let image = MemoryImage::new(&program, PAGE_SIZE as u32).unwrap();
+    let env = ExecutorEnv::builder()
+        .segment_limit_po2(14) // 16k cycles
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::new(env, image).unwrap().run().unwrap();
+    assert!(session.segments.len() > 1);
+
+    let opts = ProverOpts::succinct().with_recursion_threads(4);
+    let prover = get_prover_server(&opts).unwrap();
+    let receipt = prover
+        .prove_session(&VerifierContext::default(), &session)
+        .unwrap()
+        .receipt;
+    receipt.inner.succinct().unwrap();
+    receipt
+        .verify_integrity_with_context(&Default::default())
+        .unwrap();
+}
+
+#[test]
+fn same_claim_across_kinds() {
+    let composite = prove_nothing("poseidon2").unwrap().receipt;
+    let succinct = composite
+        .to_kind(ReceiptKind::Succinct, &VerifierContext::default())
+        .unwrap();
+    assert!(composite.same_claim(&succinct));
+    assert!(succinct.same_claim(&composite));
+
+    let mut other = succinct.clone();
+    other.journal.bytes.push(1);
+    assert!(!composite.same_claim(&other));
+}
+
+#[test]
+fn receipt_to_kind() {
+    let composite = prove_nothing("poseidon2").unwrap().receipt;
+    let ctx = VerifierContext::default();
+
+    let same = composite.to_kind(ReceiptKind::Composite, &ctx).unwrap();
+    assert_eq!(same, composite);
+
+    let succinct = composite.to_kind(ReceiptKind::Succinct, &ctx).unwrap();
+    succinct.inner.succinct().unwrap();
+    succinct.verify(MULTI_TEST_ID).unwrap();
+
+    let err = succinct
+        .to_kind(ReceiptKind::Composite, &ctx)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "cannot convert a succinct receipt to a composite receipt"
+    );
+
+    // The receipt is verified before it is converted.
+    let mut mangled = composite.clone();
+    mangled.journal.bytes.push(1);
+    assert!(mangled.to_kind(ReceiptKind::Succinct, &ctx).is_err());
+
+    // Only poseidon2 segment receipts can be lifted.
+    let sha256 = prove_nothing("sha-256").unwrap().receipt;
+    let err = sha256.to_kind(ReceiptKind::Succinct, &ctx).err().unwrap();
+    assert!(
+        err.to_string()
+            .contains("only poseidon2 segment receipts can be compressed"),
+        "{err}"
+    );
+}
+
 #[test]
 fn continuation() {
     const COUNT: usize = 2; // Number of total chunks to aim for.
@@ -613,7 +767,10 @@ mod docker {
 mod sys_verify {
     use std::{cell::RefCell, rc::Rc, sync::OnceLock};
 
-    use risc0_zkp::core::{digest::digest, hash::poseidon2::Poseidon2HashSuite};
+    use risc0_zkp::{
+        core::{digest::digest, hash::poseidon2::Poseidon2HashSuite},
+        verify::VerificationError,
+    };
     use risc0_zkvm_methods::{
         multi_test::MultiTestSpec, HELLO_COMMIT_ELF, HELLO_COMMIT_ID, MULTI_TEST_ELF, MULTI_TEST_ID,
     };
@@ -627,7 +784,8 @@ mod sys_verify {
         serde::to_vec,
         sha::Digestible,
         Assumption, CoprocessorCallback, ExecutorEnv, ExecutorEnvBuilder, ExecutorImpl, ExitCode,
-        ProveKeccakRequest, ProveZkrRequest, ProverOpts, Receipt, SuccinctReceipt, RECURSION_PO2,
+        PolicyViolation, ProveKeccakRequest, ProveZkrRequest, ProverOpts, Receipt, SuccinctReceipt,
+        VerificationPolicy, VerifierContext, RECURSION_PO2,
     };
 
     fn prove_hello_commit() -> Receipt {
@@ -713,6 +871,59 @@ mod sys_verify {
             .unwrap();
     }
 
+    #[test]
+    fn verify_with_policy() {
+        let spec = MultiTestSpec::SysVerify(vec![(
+            HELLO_COMMIT_ID.into(),
+            hello_commit_receipt().journal.bytes.clone(),
+        )]);
+        let env = ExecutorEnv::builder()
+            .write(&spec)
+            .unwrap()
+            .add_assumption(hello_commit_receipt().clone())
+            .build()
+            .unwrap();
+        let receipt = get_prover_server(&ProverOpts::fast())
+            .unwrap()
+            .prove(env, MULTI_TEST_ELF)
+            .unwrap()
+            .receipt;
+
+        let ctx = VerifierContext::default();
+        let claim = hello_commit_receipt().claim().unwrap().digest();
+        let allowed = VerificationPolicy::default().with_allowed_assumptions([claim]);
+        receipt
+            .verify_with_policy(&ctx, MULTI_TEST_ID, &allowed)
+            .unwrap();
+
+        let disallowed = VerificationPolicy::default().with_allowed_assumptions([]);
+        assert_eq!(
+            receipt
+                .verify_with_policy(&ctx, MULTI_TEST_ID, &disallowed)
+                .unwrap_err(),
+            PolicyViolation::DisallowedAssumption { claim }
+        );
+    }
+
+    #[test]
+    fn tampered_journal() {
+        let receipt = hello_commit_receipt();
+        let mut tampered = receipt.clone();
+        tampered.journal.bytes.push(1);
+        let expected = VerificationError::JournalMismatch {
+            expected: receipt.journal.digest(),
+            received: tampered.journal.digest(),
+        };
+        assert_eq!(tampered.verify(HELLO_COMMIT_ID).unwrap_err(), expected);
+
+        // Verifying with telemetry goes through the same checks.
+        let err = tampered
+            .verify_with_telemetry(&VerifierContext::default(), HELLO_COMMIT_ID)
+            .unwrap_err();
+        assert_eq!(err.phase, crate::VerifyPhase::Claim);
+        assert_eq!(err.error, expected);
+    }
+
     #[test]
     fn sys_verify_2() {
         let spec = MultiTestSpec::SysVerify(vec![(
@@ -812,6 +1023,22 @@ mod sys_verify {
             .is_err());
     }
 
+    #[test]
+    fn verify_with_exit_code() {
+        let halt_receipt = prove_halt(1);
+        halt_receipt
+            .verify_with_exit_code(MULTI_TEST_ID, ExitCode::Halted(1))
+            .unwrap();
+        assert!(matches!(
+            halt_receipt.verify_with_exit_code(MULTI_TEST_ID, ExitCode::Halted(0)),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+        assert!(matches!(
+            halt_receipt.verify(MULTI_TEST_ID),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
     #[test]
     fn sys_verify_integrity_halt_1() {
         // Generate a receipt for an execution ending in a guest error indicated
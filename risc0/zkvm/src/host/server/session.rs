@@ -15,7 +15,11 @@
 //! This module defines [Session] and [Segment] which provides a way to share
 //! execution traces between the execution phase and the proving phase.
 
-use std::{collections::BTreeSet, fs, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+};
 
 use anyhow::{ensure, Result};
 use enum_map::EnumMap;
@@ -29,8 +33,8 @@ use crate::{
         prove_info::SessionStats,
     },
     sha::Digest,
-    Assumption, AssumptionReceipt, Assumptions, ExitCode, Journal, MaybePruned, Output,
-    ReceiptClaim,
+    Assumption, AssumptionReceipt, Assumptions, ExitCode, Journal, JournalStatus, MaybePruned,
+    Output, ReceiptClaim,
 };
 
 use super::exec::syscall::{SyscallKind, SyscallMetric};
@@ -59,6 +63,9 @@ pub struct Session {
     pub input: Digest,
 
     /// The data publicly committed by the guest program.
+    ///
+    /// This is set whenever the guest produced output, including when it exited with an error.
+    /// Use [Session::journal_status] to tell whether it is the output of a successful execution.
     pub journal: Option<Journal>,
 
     /// The [ExitCode] of the session.
@@ -106,6 +113,125 @@ pub struct Session {
 
     /// syscall metrics grouped by kind.
     pub(crate) syscall_metrics: EnumMap<SyscallKind, SyscallMetric>,
+
+    /// The host syscalls invoked by the guest, by name.
+    pub(crate) syscall_summary: SyscallSummary,
+
+    /// The scope profile reported by the guest, if any.
+    pub(crate) profile_report: Option<ProfileReport>,
+
+    /// All bytes written to the journal, whether or not they became the output.
+    pub(crate) committed: Vec<u8>,
+
+    /// Largest number of bytes of stack used by the guest.
+    pub(crate) max_stack_usage: u64,
+
+    /// The data written by the guest to its host output, which is not part of the journal.
+    pub(crate) host_output: Vec<u8>,
+
+    /// The cycle breakdown of each segment, in order.
+    pub(crate) segment_stats: Vec<SegmentStats>,
+}
+
+/// The state of a paused execution, sufficient to resume it in a fresh process.
+///
+/// The memory image captures the full machine state, including registers, which are stored in
+/// system memory, and the program counter. Resume with [crate::ExecutorImpl::from_snapshot].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExecutorSnapshot {
+    pub(crate) image: MemoryImage,
+    pub(crate) state: SystemState,
+}
+
+impl ExecutorSnapshot {
+    /// The [SystemState] execution will resume from.
+    ///
+    /// This matches the `post` state of the [Session] the snapshot was taken from and the `pre`
+    /// state of the resumed [Session].
+    pub fn state(&self) -> &SystemState {
+        &self.state
+    }
+}
+
+/// A record of the host syscalls invoked by a guest during execution of a [Session].
+///
+/// Syscalls are identified by name, e.g. `risc0_zkvm_platform::syscall::nr::SYS_GETENV`, and
+/// include custom syscalls registered with [crate::ExecutorEnvBuilder::io_callback].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyscallSummary {
+    counts: BTreeMap<String, u64>,
+}
+
+impl SyscallSummary {
+    pub(crate) fn record(&mut self, name: &str) {
+        // Only allocate the first time a syscall is seen, since this runs on every syscall.
+        if let Some(count) = self.counts.get_mut(name) {
+            *count += 1;
+        } else {
+            self.counts.insert(name.to_string(), 1);
+        }
+    }
+
+    /// Returns the number of times the named syscall was invoked.
+    pub fn count(&self, name: &str) -> u64 {
+        self.counts.get(name).copied().unwrap_or_default()
+    }
+
+    /// Returns true if the named syscall was invoked at least once.
+    pub fn contains(&self, name: &str) -> bool {
+        self.counts.contains_key(name)
+    }
+
+    /// Returns true if the guest invoked no syscalls.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Iterate over the invoked syscalls and their counts, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.counts
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+}
+
+/// The cycles spent in the guest scopes recorded with `risc0_zkvm::guest::profile_scope!`.
+///
+/// Scopes are listed in the order the guest first entered them. Cycle counts are user cycles, as
+/// reported by `env::cycle_count`, and include the cycles of nested scopes.
+///
+/// A report covers a single [Session]. When the guest pauses, the scopes recorded so far are
+/// reported in the paused session, and the session that resumes it only reports the scopes
+/// exited after it resumed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileReport {
+    pub(crate) scopes: Vec<ScopeProfile>,
+}
+
+/// The profile of a single named scope in a [ProfileReport].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ScopeProfile {
+    /// Name given to the scope by the guest.
+    pub name: String,
+
+    /// Total user cycles spent in the scope, over all entries.
+    pub cycles: u64,
+
+    /// Number of times the scope was entered.
+    pub count: u64,
+}
+
+impl ProfileReport {
+    /// Returns the profile of the named scope, if the guest recorded it.
+    pub fn get(&self, name: &str) -> Option<&ScopeProfile> {
+        self.scopes.iter().find(|scope| scope.name == name)
+    }
+
+    /// Iterate over the recorded scopes, in the order they were first entered.
+    pub fn iter(&self) -> impl Iterator<Item = &ScopeProfile> {
+        self.scopes.iter()
+    }
 }
 
 /// The execution trace of a portion of a program.
@@ -134,6 +260,23 @@ impl Segment {
     }
 }
 
+/// The cycles spent by a single [Segment].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SegmentStats {
+    /// The index of the [Segment] within the [Session].
+    pub index: u32,
+
+    /// The power of two length of the [Segment].
+    pub po2: usize,
+
+    /// The number of cycles spent executing guest instructions.
+    pub user_cycles: u64,
+
+    /// The number of cycles spent loading and storing pages of memory.
+    pub paging_cycles: u64,
+}
+
 /// A reference to a [Segment].
 ///
 /// This allows implementers to determine the best way to represent this in an
@@ -175,6 +318,12 @@ impl Session {
         pending_keccaks: Vec<ProveKeccakRequest>,
         ecall_metrics: Vec<(String, EcallMetric)>,
         syscall_metrics: EnumMap<SyscallKind, SyscallMetric>,
+        syscall_summary: SyscallSummary,
+        profile_report: Option<ProfileReport>,
+        committed: Vec<u8>,
+        max_stack_usage: u64,
+        host_output: Vec<u8>,
+        segment_stats: Vec<SegmentStats>,
     ) -> Self {
         Self {
             segments,
@@ -194,6 +343,12 @@ impl Session {
             pending_keccaks,
             ecall_metrics,
             syscall_metrics,
+            syscall_summary,
+            profile_report,
+            committed,
+            max_stack_usage,
+            host_output,
+            segment_stats,
         }
     }
 
@@ -310,6 +465,99 @@ impl Session {
         );
     }
 
+    /// Capture the state of a paused [Session] so that execution can be resumed later.
+    ///
+    /// Returns an error unless the [Session] ended with [ExitCode::Paused].
+    pub fn snapshot(&self) -> Result<ExecutorSnapshot> {
+        ensure!(
+            matches!(self.exit_code, ExitCode::Paused(_)),
+            "Session with exit code {:?} cannot be resumed",
+            self.exit_code
+        );
+        Ok(ExecutorSnapshot {
+            image: self.post_image.clone(),
+            state: self.post_state.clone(),
+        })
+    }
+
+    /// Returns the user exit code the guest halted or paused with.
+    ///
+    /// This is the code passed to `env::halt_with_code`, `env::exit` or `env::pause`, and is `0`
+    /// if the [Session] ended for any other reason. The same code is committed to the receipt
+    /// claim and can be checked by a verifier with [crate::Receipt::exit_code].
+    pub fn user_exit_code(&self) -> u32 {
+        match self.exit_code {
+            ExitCode::Halted(code) | ExitCode::Paused(code) => code,
+            ExitCode::SystemSplit | ExitCode::SessionLimit => 0,
+        }
+    }
+
+    /// Returns whether [Session::journal()] is the complete output of the guest.
+    ///
+    /// The journal is [JournalStatus::Complete] whenever the guest halted with a user exit code,
+    /// including non-zero codes from `env::halt_with_code`. It is [JournalStatus::Partial] when
+    /// the guest paused or exited with a code reserved by the zkVM, in which case it should not be
+    /// treated as the result of the guest.
+    pub fn journal_status(&self) -> JournalStatus {
+        JournalStatus::from_exit_code(self.exit_code)
+    }
+
+    /// Returns the data publicly committed by the guest, which is the journal of the receipt.
+    ///
+    /// This is the same as the `journal` field. Only this data is part of the [ReceiptClaim];
+    /// see [Session::host_output] for auxiliary data written by the guest.
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// Returns the data the guest wrote to its host output during this [Session].
+    ///
+    /// The guest writes to the host output with `env::commit_host_output`. This data is not part
+    /// of the journal or the [ReceiptClaim], and is not checked when verifying a receipt.
+    pub fn host_output(&self) -> &[u8] {
+        &self.host_output
+    }
+
+    /// Returns all bytes the guest wrote to the journal during this [Session].
+    ///
+    /// Unlike [Session::journal()], this includes data committed by a guest that did not produce
+    /// any output, and is never proven. It is meant for debugging failed executions.
+    pub fn committed(&self) -> &[u8] {
+        &self.committed
+    }
+
+    /// Returns the largest number of bytes of stack used by the guest during this [Session].
+    ///
+    /// This is the distance from the top of the stack to the lowest address the stack pointer
+    /// was set to, and can be compared against the size of the stack region to check how close a
+    /// guest comes to overflowing it. See [crate::ExecutorEnvBuilder::stack_guard].
+    pub fn max_stack_usage(&self) -> u64 {
+        self.max_stack_usage
+    }
+
+    /// Returns the host syscalls invoked by the guest during this [Session].
+    pub fn syscall_summary(&self) -> &SyscallSummary {
+        &self.syscall_summary
+    }
+
+    /// Returns the cycles spent in the scopes recorded by the guest with
+    /// `risc0_zkvm::guest::profile_scope!`.
+    ///
+    /// This is `None` if the guest was built without the `profile-scope` feature of
+    /// `risc0-zkvm`, or did not record any scope. The report only covers this session, and not
+    /// the sessions before a pause it was resumed from.
+    pub fn profile_report(&self) -> Option<&ProfileReport> {
+        self.profile_report.as_ref()
+    }
+
+    /// Returns the cycle breakdown of each segment of this [Session], in order.
+    ///
+    /// This shows how the paging cycles of [Session::stats] are spread over the segments. The
+    /// paging cycles of a segment grow with the number of distinct pages it reads and writes.
+    pub fn segment_stats(&self) -> &[SegmentStats] {
+        &self.segment_stats
+    }
+
     /// Returns stats for the session
     ///
     /// This contains cycle and segment information about the session useful for debugging and measuring performance.
@@ -372,11 +620,19 @@ impl SimpleSegmentRef {
 pub struct FileSegmentRef {
     path: PathBuf,
     _dir: SegmentPath,
+    #[cfg(feature = "zstd")]
+    compressed: bool,
 }
 
 impl SegmentRef for FileSegmentRef {
     fn resolve(&self) -> Result<Segment> {
         let contents = fs::read(&self.path)?;
+        #[cfg(feature = "zstd")]
+        let contents = if self.compressed {
+            zstd::decode_all(contents.as_slice())?
+        } else {
+            contents
+        };
         let segment = bincode::deserialize(&contents)?;
         Ok(segment)
     }
@@ -392,6 +648,24 @@ impl FileSegmentRef {
         Ok(Self {
             path,
             _dir: dir.clone(),
+            #[cfg(feature = "zstd")]
+            compressed: false,
+        })
+    }
+
+    /// Construct a [FileSegmentRef] that stores a zstd-compressed `segment`.
+    ///
+    /// The `level` is passed to zstd, where `0` selects its default level. The segment is
+    /// transparently decompressed by [SegmentRef::resolve].
+    #[cfg(feature = "zstd")]
+    pub fn with_compression(segment: &Segment, dir: &SegmentPath, level: i32) -> Result<Self> {
+        let path = dir.path().join(format!("{}.bincode.zst", segment.index));
+        let contents = zstd::encode_all(bincode::serialize(&segment)?.as_slice(), level)?;
+        fs::write(&path, contents)?;
+        Ok(Self {
+            path,
+            _dir: dir.clone(),
+            compressed: true,
         })
     }
 }
@@ -57,6 +57,7 @@
 //! | metal            | macos             | prove, std | Deprecated - Metal GPU acceleration for the prover is now enabled by default on Apple Silicon.                                                               |
 //! | prove            | all except rv32im | std        | Enables the prover, incompatible within the zkvm guest.                                                                                                      |
 //! | std              | all               |            | Support for the Rust stdlib.                                                                                                                                 |
+//! | zstd             | all except rv32im | prove, std | Enables compression of segments written to disk by [FileSegmentRef].                                                                                         |
 //!
 //! [`cargo risczero` tool]: https://crates.io/crates/cargo-risczero
 //! [dev-docs]: https://dev.risczero.com
@@ -105,11 +106,16 @@ pub use {
             RECURSION_PO2,
         },
         server::{
-            exec::executor::ExecutorImpl,
-            prove::{get_prover_server, HalPair, ProverServer},
+            exec::{
+                error::{ExecutorError, MemoryFaultKind},
+                executor::ExecutorImpl,
+                replay::replay_receipt,
+            },
+            prove::{get_prover_server, HalPair, ProverServer, ProverSetup},
             session::{
-                FileSegmentRef, NullSegmentRef, Segment, SegmentRef, Session, SessionEvents,
-                SimpleSegmentRef,
+                ExecutorSnapshot, FileSegmentRef, NullSegmentRef, ProfileReport, ScopeProfile,
+                Segment, SegmentRef, SegmentStats, Session, SessionEvents, SimpleSegmentRef,
+                SyscallSummary,
             },
         },
     },
@@ -132,12 +138,13 @@ pub use {
             SessionInfo,
         },
         client::{
-            env::{ExecutorEnv, ExecutorEnvBuilder},
+            env::{ExecutorEnv, ExecutorEnvBuilder, SegmentSplitInfo, SegmentSplitReason},
             prove::{
                 default_executor, default_prover, external::ExternalProver, Executor, Prover,
                 ProverOpts, ReceiptKind,
             },
         },
+        guest_elf::{validate_guest_elf, ElfValidationError, GuestElfInfo},
     },
     risc0_circuit_rv32im::trace::{TraceCallback, TraceEvent},
 };
@@ -163,11 +170,14 @@ pub use {
     risc0_groth16::Seal as Groth16Seal,
 };
 
+#[cfg(all(feature = "std", not(target_os = "zkvm")))]
+pub use receipt::VerifyTelemetry;
 pub use receipt::{
-    AssumptionReceipt, CompositeReceipt, CompositeReceiptVerifierParameters, FakeReceipt,
-    InnerAssumptionReceipt, InnerReceipt, Journal, Receipt, ReceiptMetadata, SegmentReceipt,
-    SegmentReceiptVerifierParameters, SuccinctReceipt, SuccinctReceiptVerifierParameters,
-    VerifierContext, DEFAULT_MAX_PO2,
+    AssumptionReceipt, CompositeReceipt, CompositeReceiptVerifierParameters, CompositionError,
+    FakeReceipt, InnerAssumptionReceipt, InnerReceipt, Journal, JournalStatus, PolicyViolation,
+    ProtocolVersion, Receipt, ReceiptMetadata, SegmentReceipt, SegmentReceiptVerifierParameters,
+    SuccinctReceipt, SuccinctReceiptVerifierParameters, VerificationPolicy, VerifierBundle,
+    VerifierContext, VerifyPhase, VerifyPhaseError, DEFAULT_MAX_PO2,
 };
 //#[cfg(any(not(target_os = "zkvm"), feature = "std"))]
 pub use receipt::{Groth16Receipt, Groth16ReceiptVerifierParameters};
@@ -36,6 +36,7 @@ use risc0_zkp::{
     },
     verify::VerificationError,
 };
+use risc0_zkvm_platform::WORD_SIZE;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 // Make succinct receipt available through this `receipt` module.
@@ -43,13 +44,13 @@ use crate::{
     receipt_claim::Unknown,
     serde::{from_slice, Error},
     sha::{Digestible, Sha256},
-    Assumption, Assumptions, MaybePruned, Output, ReceiptClaim,
+    Assumption, Assumptions, ExitCode, MaybePruned, Output, ReceiptClaim,
 };
 
 pub use self::groth16::{Groth16Receipt, Groth16ReceiptVerifierParameters};
 
 pub use self::{
-    composite::{CompositeReceipt, CompositeReceiptVerifierParameters},
+    composite::{CompositeReceipt, CompositeReceiptVerifierParameters, CompositionError},
     segment::{SegmentReceipt, SegmentReceiptVerifierParameters},
     succinct::{SuccinctReceipt, SuccinctReceiptVerifierParameters},
 };
@@ -159,41 +160,233 @@ impl Receipt {
     /// Uses the zero-knowledge proof system to verify the seal, and decodes the proven
     /// [ReceiptClaim]. This method additionally ensures that the guest exited with a successful
     /// status code (i.e. `Halted(0)`), the image ID is as expected, and the journal has not been
-    /// tampered with.
+    /// tampered with. A journal that differs from the one committed to by the claim is reported
+    /// as [VerificationError::JournalMismatch].
     pub fn verify_with_context(
         &self,
         ctx: &VerifierContext,
         image_id: impl Into<Digest>,
     ) -> Result<(), VerificationError> {
+        self.verify_with_context_and_exit_code(ctx, image_id, ExitCode::Halted(0))
+    }
+
+    /// Verify that this receipt proves an execution of the zkVM from the given `image_id` that
+    /// ended with the given `exit_code`.
+    ///
+    /// This performs the same checks as [Receipt::verify], except that the guest may have exited
+    /// with any expected status, such as a non-zero code passed to `env::halt_with_code`. Returns
+    /// [VerificationError::ClaimDigestMismatch] if the receipt proves a different exit code.
+    pub fn verify_with_exit_code(
+        &self,
+        image_id: impl Into<Digest>,
+        exit_code: ExitCode,
+    ) -> Result<(), VerificationError> {
+        self.verify_with_context_and_exit_code(&VerifierContext::default(), image_id, exit_code)
+    }
+
+    /// Verify that this receipt proves an execution of the zkVM from the given `image_id` that
+    /// ended with the given `exit_code`, as in [Receipt::verify_with_exit_code].
+    pub fn verify_with_context_and_exit_code(
+        &self,
+        ctx: &VerifierContext,
+        image_id: impl Into<Digest>,
+        exit_code: ExitCode,
+    ) -> Result<(), VerificationError> {
+        self.verify_phases(ctx, image_id.into(), exit_code, |_| {})
+            .map_err(|err| err.error)
+    }
+
+    /// Verify this receipt as in [Receipt::verify_with_context_and_exit_code], calling
+    /// `on_phase` as each [VerifyPhase] completes.
+    fn verify_phases(
+        &self,
+        ctx: &VerifierContext,
+        image_id: Digest,
+        exit_code: ExitCode,
+        mut on_phase: impl FnMut(VerifyPhase),
+    ) -> Result<(), VerifyPhaseError> {
+        let fail = |phase| move |error| VerifyPhaseError { phase, error };
+
         if self.inner.verifier_parameters() != self.metadata.verifier_parameters {
-            return Err(VerificationError::VerifierParametersMismatch {
-                expected: self.inner.verifier_parameters(),
-                received: self.metadata.verifier_parameters,
+            return Err(VerifyPhaseError {
+                phase: VerifyPhase::Parameters,
+                error: VerificationError::VerifierParametersMismatch {
+                    expected: self.inner.verifier_parameters(),
+                    received: self.metadata.verifier_parameters,
+                },
             });
         }
-
-        tracing::debug!("Receipt::verify_with_context");
-        self.inner.verify_integrity_with_context(ctx)?;
+        let version_ctx = ctx
+            .for_receipt_version(&self.inner)
+            .map_err(fail(VerifyPhase::Parameters))?;
+        let ctx = version_ctx.as_ref().unwrap_or(ctx);
+        on_phase(VerifyPhase::Parameters);
+
+        tracing::debug!("Receipt::verify_phases");
+        match &self.inner {
+            InnerReceipt::Composite(inner) => {
+                inner
+                    .verify_segments_with_context(ctx)
+                    .map_err(fail(VerifyPhase::Seal))?;
+                on_phase(VerifyPhase::Seal);
+                inner
+                    .verify_assumptions_with_context(ctx)
+                    .map_err(fail(VerifyPhase::Assumptions))?;
+                on_phase(VerifyPhase::Assumptions);
+            }
+            inner => {
+                inner
+                    .verify_integrity_with_context(ctx)
+                    .map_err(fail(VerifyPhase::Seal))?;
+                on_phase(VerifyPhase::Seal);
+            }
+        }
 
         // Check that the claim on the verified receipt matches what was expected. Since we have
         // constrained all field in the ReceiptClaim, we can directly construct the expected digest
         // and do not need to open the claim digest on the inner receipt.
-        let expected_claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(self.journal.digest()));
-        if expected_claim.digest() != self.inner.claim()?.digest() {
+        let expected_claim = ReceiptClaim {
+            exit_code,
+            ..ReceiptClaim::ok(image_id, MaybePruned::Pruned(self.journal.digest()))
+        };
+        let claim = self.inner.claim().map_err(fail(VerifyPhase::Claim))?;
+        if expected_claim.digest() != claim.digest() {
             tracing::debug!(
                 "receipt claim does not match expected claim:\nreceipt: {:#?}\nexpected: {:#?}",
-                self.inner.claim()?,
+                claim,
                 expected_claim
             );
-            return Err(VerificationError::ClaimDigestMismatch {
-                expected: expected_claim.digest(),
-                received: self.claim()?.digest(),
+            // Report a swapped journal as such, if the claim commits to a different journal.
+            let committed = claim
+                .as_value()
+                .ok()
+                .and_then(|claim| claim.output.as_value().ok())
+                .and_then(|output| output.as_ref());
+            if let Some(output) = committed {
+                let expected = output.journal.digest();
+                let received = self.journal.digest();
+                if expected != received {
+                    return Err(VerifyPhaseError {
+                        phase: VerifyPhase::Claim,
+                        error: VerificationError::JournalMismatch { expected, received },
+                    });
+                }
+            }
+            return Err(VerifyPhaseError {
+                phase: VerifyPhase::Claim,
+                error: VerificationError::ClaimDigestMismatch {
+                    expected: expected_claim.digest(),
+                    received: claim.digest(),
+                },
             });
         }
+        on_phase(VerifyPhase::Claim);
+
+        Ok(())
+    }
 
+    /// Verify that this receipt proves a successful execution of the zkVM from the given
+    /// `image_id`, and that it satisfies the given [VerificationPolicy].
+    ///
+    /// This performs the same checks as [Receipt::verify_with_context], and then checks the
+    /// assumptions resolved by this receipt against the claims allowed by the policy.
+    pub fn verify_with_policy(
+        &self,
+        ctx: &VerifierContext,
+        image_id: impl Into<Digest>,
+        policy: &VerificationPolicy,
+    ) -> Result<(), PolicyViolation> {
+        self.verify_with_context(ctx, image_id)?;
+
+        if let Some(allowed) = &policy.allowed_assumptions {
+            // Compressing a receipt resolves its assumptions, after which the claims of the
+            // assumptions are no longer recorded in the receipt.
+            let InnerReceipt::Composite(inner) = &self.inner else {
+                return Err(PolicyViolation::AssumptionsNotRecorded);
+            };
+            for assumption in inner.assumption_receipts.iter() {
+                let claim = assumption.claim_digest()?;
+                if !allowed.contains(&claim) {
+                    return Err(PolicyViolation::DisallowedAssumption { claim });
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Verify that this receipt attests to the claim with the given digest.
+    ///
+    /// This is intended for verifying many receipts against a fixed, precomputed claim digest,
+    /// e.g. one computed once with [ReceiptClaim::ok] from the expected image ID and journal. The
+    /// claim digest is checked before the seal, and neither the journal nor the image ID is
+    /// hashed. Note that the journal carried by this receipt is not checked; the expected claim
+    /// must commit to the expected journal.
+    ///
+    /// Returns [VerificationError::ClaimDigestMismatch] if this receipt attests to a different
+    /// claim.
+    pub fn verify_claim(
+        &self,
+        expected_claim_digest: Digest,
+        ctx: &VerifierContext,
+    ) -> Result<(), VerificationError> {
+        if self.inner.verifier_parameters() != self.metadata.verifier_parameters {
+            return Err(VerificationError::VerifierParametersMismatch {
+                expected: self.inner.verifier_parameters(),
+                received: self.metadata.verifier_parameters,
+            });
+        }
+        let version_ctx = ctx.for_receipt_version(&self.inner)?;
+        let ctx = version_ctx.as_ref().unwrap_or(ctx);
+
+        let received = self.inner.claim()?.digest();
+        if received != expected_claim_digest {
+            return Err(VerificationError::ClaimDigestMismatch {
+                expected: expected_claim_digest,
+                received,
+            });
+        }
+
+        tracing::debug!("Receipt::verify_claim");
+        self.inner.verify_integrity_with_context(ctx)
+    }
+
+    /// Verify this receipt as in [Receipt::verify_with_context], recording the time spent in each
+    /// phase of verification.
+    ///
+    /// On failure, the returned error identifies the [VerifyPhase] that failed.
+    #[cfg(all(feature = "std", not(target_os = "zkvm")))]
+    pub fn verify_with_telemetry(
+        &self,
+        ctx: &VerifierContext,
+        image_id: impl Into<Digest>,
+    ) -> Result<VerifyTelemetry, VerifyPhaseError> {
+        use std::time::Instant;
+
+        let mut telemetry = VerifyTelemetry::default();
+        let mut start = Instant::now();
+        self.verify_phases(ctx, image_id.into(), ExitCode::Halted(0), |phase| {
+            let elapsed = start.elapsed();
+            match phase {
+                VerifyPhase::Seal => telemetry.seal = elapsed,
+                VerifyPhase::Assumptions => telemetry.assumptions = elapsed,
+                VerifyPhase::Claim => telemetry.claim = elapsed,
+                VerifyPhase::Parameters => {}
+            }
+            start = Instant::now();
+        })?;
+        Ok(telemetry)
+    }
+
+    /// Verify that this receipt proves a successful execution of the given guest ELF.
+    ///
+    /// Computes the image ID of `elf` and verifies against it as in [Receipt::verify]. An error is
+    /// returned if the ELF is malformed, before the seal is checked.
+    #[cfg(not(target_os = "zkvm"))]
+    pub fn verify_against_elf(&self, elf: &[u8]) -> Result<()> {
+        let image_id = risc0_binfmt::compute_image_id(elf)?;
+        Ok(self.verify(image_id)?)
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim and journal
     /// are attested to by the seal.
     ///
@@ -205,6 +398,9 @@ impl Receipt {
     /// relevant to their application. If you need to verify a successful
     /// guest execution and access the journal, the `verify` function is
     /// recommended.
+    ///
+    /// If the journal of this receipt is not the one committed to by the claim, e.g. because it
+    /// was replaced after proving, this returns [VerificationError::JournalMismatch].
     pub fn verify_integrity_with_context(
         &self,
         ctx: &VerifierContext,
@@ -215,6 +411,8 @@ impl Receipt {
                 received: self.metadata.verifier_parameters,
             });
         }
+        let version_ctx = ctx.for_receipt_version(&self.inner)?;
+        let ctx = version_ctx.as_ref().unwrap_or(ctx);
 
         tracing::debug!("Receipt::verify_integrity_with_context");
         self.inner.verify_integrity_with_context(ctx)?;
@@ -244,6 +442,15 @@ impl Receipt {
                     hex::encode(expected_output.digest()),
                     hex::encode(claim.output.digest()),
                 );
+                // Report the journal digest committed to by the claim if it can be opened, to
+                // distinguish a swapped journal from e.g. unresolved assumptions.
+                if let Ok(Some(output)) = claim.output.as_value() {
+                    let expected = output.journal.digest();
+                    let received = self.journal.digest();
+                    if expected != received {
+                        return Err(VerificationError::JournalMismatch { expected, received });
+                    }
+                }
                 return Err(VerificationError::JournalDigestMismatch);
             }
             tracing::debug!("accepting zero digest for output of receipt with empty journal");
@@ -257,12 +464,131 @@ impl Receipt {
         self.inner.claim()
     }
 
+    /// Returns the digest of the [ReceiptClaim] attested to by this receipt.
+    ///
+    /// The digest is independent of the receipt kind, so a composite, succinct, and Groth16
+    /// receipt for the same execution all return the same value.
+    pub fn claim_digest(&self) -> Result<Digest, VerificationError> {
+        Ok(self.claim()?.digest())
+    }
+
+    /// Returns true if this receipt and `other` attest to the same [ReceiptClaim] and carry the
+    /// same journal.
+    ///
+    /// Seals are not compared or verified, which makes this useful for cross-checking receipts
+    /// produced by different provers, including receipts of different kinds.
+    pub fn same_claim(&self, other: &Receipt) -> bool {
+        match (self.claim_digest(), other.claim_digest()) {
+            (Ok(a), Ok(b)) => a == b && self.journal == other.journal,
+            _ => false,
+        }
+    }
+
+    /// Returns the [ExitCode] attested to by this receipt.
+    ///
+    /// This reads the exit code from the receipt claim without verifying the seal. Call
+    /// [Receipt::verify] or [Receipt::verify_integrity_with_context] first to ensure the claim is
+    /// attested to. Returns [VerificationError::ReceiptFormatError] if the claim is pruned.
+    pub fn exit_code(&self) -> Result<ExitCode, VerificationError> {
+        let claim = self.claim()?;
+        let claim = claim
+            .as_value()
+            .map_err(|_| VerificationError::ReceiptFormatError)?;
+        Ok(claim.exit_code)
+    }
+
+    /// Returns whether the journal of this receipt is the complete output of the guest, according
+    /// to the exit code in its claim.
+    ///
+    /// [Receipt::verify] only accepts receipts that halted with exit code 0, which always have a
+    /// complete journal. When the receipt is checked with
+    /// [Receipt::verify_integrity_with_context] instead, check this before using the journal as
+    /// the result of the guest.
+    pub fn journal_status(&self) -> Result<JournalStatus, VerificationError> {
+        Ok(JournalStatus::from_exit_code(self.exit_code()?))
+    }
+
+    /// Returns the receipt embedded in this receipt to resolve the assumption with the given
+    /// claim digest, if present.
+    ///
+    /// Only a [CompositeReceipt] carries the receipts for its resolved assumptions; for other
+    /// receipt kinds this always returns `None`. The returned receipt can be checked on its own
+    /// with [InnerAssumptionReceipt::verify_integrity_with_context]. Since the journal of the
+    /// assumption is not part of the outer receipt, it cannot be verified against an image ID
+    /// as a standalone [Receipt].
+    pub fn resolved_assumption(&self, claim_digest: Digest) -> Option<&InnerAssumptionReceipt> {
+        let InnerReceipt::Composite(inner) = &self.inner else {
+            return None;
+        };
+        inner
+            .assumption_receipts
+            .iter()
+            .find(|receipt| receipt.claim_digest().ok() == Some(claim_digest))
+    }
+
     /// Total number of bytes used by the seals of this receipt.
     pub fn seal_size(&self) -> usize {
         self.inner.seal_size()
     }
 }
 
+/// Time spent in each phase of [Receipt::verify_with_telemetry].
+#[cfg(all(feature = "std", not(target_os = "zkvm")))]
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct VerifyTelemetry {
+    /// Time spent verifying the seal of the receipt.
+    pub seal: std::time::Duration,
+
+    /// Time spent verifying the receipts resolving the assumptions of a composite receipt.
+    ///
+    /// This is zero for other receipt kinds, which have no attached assumption receipts.
+    pub assumptions: std::time::Duration,
+
+    /// Time spent hashing and comparing the receipt claim.
+    pub claim: std::time::Duration,
+}
+
+/// A phase of [Receipt::verify_with_telemetry].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifyPhase {
+    /// Checking the verifier parameters of the receipt against its metadata.
+    Parameters,
+
+    /// Verifying the seal of the receipt.
+    Seal,
+
+    /// Verifying the receipts resolving the assumptions of a composite receipt.
+    Assumptions,
+
+    /// Checking the receipt claim against the expected claim.
+    Claim,
+}
+
+/// Error returned by [Receipt::verify_with_telemetry], identifying the phase that failed.
+#[derive(Debug, PartialEq)]
+pub struct VerifyPhaseError {
+    /// The phase of verification that failed.
+    pub phase: VerifyPhase,
+
+    /// The underlying verification error.
+    pub error: VerificationError,
+}
+
+impl core::fmt::Display for VerifyPhaseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "verification failed in {:?} phase: {}",
+            self.phase, self.error
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifyPhaseError {}
+
 /// A record of the public commitments for a proven zkVM execution.
 ///
 /// Public outputs, including commitments to important inputs, are written to the journal during
@@ -286,6 +612,21 @@ impl Journal {
     pub fn decode<T: DeserializeOwned>(&self) -> Result<T, Error> {
         from_slice(&self.bytes)
     }
+
+    /// Decode the value committed at the given byte offset, as returned by
+    /// `env::commit_at` in the guest.
+    ///
+    /// Bytes after the value are ignored, so values can be read in any order. Returns
+    /// [Error::DeserializeUnexpectedEnd] if the offset is past the end of the journal, or if the
+    /// bytes from the offset to the end of the journal are not a whole number of words.
+    pub fn value_at<T: DeserializeOwned>(&self, offset: usize) -> Result<T, Error> {
+        let bytes = self
+            .bytes
+            .get(offset..)
+            .filter(|bytes| bytes.len() % WORD_SIZE == 0)
+            .ok_or(Error::DeserializeUnexpectedEnd)?;
+        from_slice(bytes)
+    }
 }
 
 impl risc0_binfmt::Digestible for Journal {
@@ -300,6 +641,43 @@ impl AsRef<[u8]> for Journal {
     }
 }
 
+/// Whether a journal is the final output of the guest.
+///
+/// A guest that halts with a user exit code, i.e. at most
+/// [MAX_USER_EXIT_CODE](crate::guest::env::MAX_USER_EXIT_CODE), has finished and its journal is
+/// complete, whatever the code. A guest that pauses, stops at a split or limit, or exits with a
+/// code reserved by the zkVM (such as
+/// [REQUIRE_FAILED_EXIT_CODE](crate::guest::env::REQUIRE_FAILED_EXIT_CODE)) still produces a
+/// journal, and that journal is proven like any other. It only holds what was committed up to
+/// that point though, and should not be treated as the result of the guest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JournalStatus {
+    /// The guest halted with a user exit code and the journal is its complete output.
+    Complete,
+
+    /// The guest paused, was stopped, or exited with a reserved code, and the journal holds only
+    /// the data committed before that point.
+    Partial,
+}
+
+impl JournalStatus {
+    /// Returns the status of the journal produced by an execution that ended with `exit_code`.
+    pub fn from_exit_code(exit_code: ExitCode) -> Self {
+        match exit_code {
+            ExitCode::Halted(code) if code <= crate::guest::env::MAX_USER_EXIT_CODE => {
+                Self::Complete
+            }
+            _ => Self::Partial,
+        }
+    }
+
+    /// True if the journal is the complete output of the guest.
+    pub fn is_complete(&self) -> bool {
+        *self == Self::Complete
+    }
+}
+
 /// A lower level receipt, containing the cryptographic seal (i.e. zero-knowledge proof) and
 /// verification logic for a specific proof system and circuit. All inner receipt types are
 /// zero-knowledge proofs of execution for a RISC-V zkVM.
@@ -667,6 +1045,72 @@ impl From<InnerReceipt> for InnerAssumptionReceipt {
     }
 }
 
+/// Additional requirements checked by [Receipt::verify_with_policy].
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct VerificationPolicy {
+    /// Digests of the claims the receipt is allowed to resolve as assumptions.
+    ///
+    /// When `None`, any assumption is allowed. When set, the receipt must be a
+    /// [CompositeReceipt], since compressed receipts do not record which assumptions they
+    /// resolved.
+    pub allowed_assumptions: Option<Vec<Digest>>,
+}
+
+impl VerificationPolicy {
+    /// Only allow the receipt to resolve assumptions with the given claim digests.
+    pub fn with_allowed_assumptions(mut self, claims: impl IntoIterator<Item = Digest>) -> Self {
+        self.allowed_assumptions = Some(claims.into_iter().collect());
+        self
+    }
+}
+
+/// Reasons a receipt is rejected by [Receipt::verify_with_policy].
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PolicyViolation {
+    /// The receipt failed verification.
+    Verification(VerificationError),
+
+    /// The receipt resolved an assumption with a claim that is not allowed by the policy.
+    DisallowedAssumption {
+        /// Digest of the claim of the assumption.
+        claim: Digest,
+    },
+
+    /// The policy restricts assumptions, but the receipt does not record the assumptions it
+    /// resolved.
+    AssumptionsNotRecorded,
+}
+
+impl From<VerificationError> for PolicyViolation {
+    fn from(err: VerificationError) -> Self {
+        Self::Verification(err)
+    }
+}
+
+impl core::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Verification(err) => write!(f, "{err}"),
+            Self::DisallowedAssumption { claim } => {
+                write!(
+                    f,
+                    "receipt resolved a disallowed assumption with claim {claim}"
+                )
+            }
+            Self::AssumptionsNotRecorded => write!(
+                f,
+                "receipt does not record its assumptions, which the policy requires; verify \
+                 the composite receipt before compression"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PolicyViolation {}
+
 /// Maximum segment size, as a power of two (po2) that the default verifier parameters will accept.
 ///
 /// A default of 21 was selected to reach a target of 97 bits of security under our analysis. Using
@@ -687,6 +1131,13 @@ pub struct VerifierContext {
 
     /// Parameters for verification of [Groth16Receipt].
     pub groth16_verifier_parameters: Option<Groth16ReceiptVerifierParameters>,
+
+    /// Protocol versions a [Receipt] may have been produced under.
+    ///
+    /// When empty, only receipts produced for the parameters above are accepted. Otherwise, a
+    /// receipt is verified with the parameters of the listed version it was produced for, and is
+    /// rejected if it matches none of them. See [VerifierContext::with_allowed_versions].
+    pub allowed_versions: Vec<ProtocolVersion>,
 }
 
 impl VerifierContext {
@@ -697,6 +1148,7 @@ impl VerifierContext {
             segment_verifier_parameters: None,
             succinct_verifier_parameters: None,
             groth16_verifier_parameters: None,
+            allowed_versions: Vec::new(),
         }
     }
 
@@ -725,6 +1177,7 @@ impl VerifierContext {
             groth16_verifier_parameters: Some(Groth16ReceiptVerifierParameters::from_max_po2(
                 po2_max,
             )),
+            allowed_versions: Vec::new(),
         }
     }
 
@@ -768,6 +1221,54 @@ impl VerifierContext {
         self
     }
 
+    /// Return [VerifierContext] that accepts receipts produced under any of the given protocol
+    /// versions.
+    ///
+    /// This is an explicit opt-in to accepting receipts from other, e.g. older, releases of the
+    /// zkVM. Only the listed versions are accepted, so the current version, given by
+    /// [ProtocolVersion::default], must be included for current receipts to verify. Receipts from
+    /// a version that matches none of them are rejected with
+    /// [VerificationError::UnsupportedProtocolVersion].
+    pub fn with_allowed_versions(
+        mut self,
+        versions: impl IntoIterator<Item = ProtocolVersion>,
+    ) -> Self {
+        self.allowed_versions = versions.into_iter().collect();
+        self
+    }
+
+    /// Returns a context with the parameters of the allowed version `inner` was produced for, or
+    /// none if no allowed versions are set and this context should be used as is.
+    fn for_receipt_version(
+        &self,
+        inner: &InnerReceipt,
+    ) -> Result<Option<VerifierContext>, VerificationError> {
+        // Fake receipts have no version to check.
+        if self.allowed_versions.is_empty() || matches!(inner, InnerReceipt::Fake(_)) {
+            return Ok(None);
+        }
+
+        let found = inner.verifier_parameters();
+        let allowed: Vec<Digest> = self
+            .allowed_versions
+            .iter()
+            .map(|version| version.verifier_parameters(inner))
+            .collect();
+        let version = allowed
+            .iter()
+            .position(|digest| *digest == found)
+            .map(|idx| &self.allowed_versions[idx])
+            .ok_or(VerificationError::UnsupportedProtocolVersion { found, allowed })?;
+
+        Ok(Some(VerifierContext {
+            suites: self.suites.clone(),
+            segment_verifier_parameters: Some(version.segment.clone()),
+            succinct_verifier_parameters: Some(version.succinct.clone()),
+            groth16_verifier_parameters: Some(version.groth16.clone()),
+            allowed_versions: Vec::new(),
+        }))
+    }
+
     /// Parameters for verification of [CompositeReceipt].
     ///
     /// Made up of the verifier parameters for each other receipt type. Returns none if any of the
@@ -779,6 +1280,76 @@ impl VerifierContext {
             groth16: self.groth16_verifier_parameters.as_ref()?.clone().into(),
         })
     }
+
+    /// Verify each of the given receipts against `image_id` with this context.
+    ///
+    /// Returns one result per receipt, in the same order, as returned by
+    /// [Receipt::verify_with_context]. A failing receipt does not stop verification of the
+    /// others.
+    ///
+    /// Receipts are verified on multiple threads when all hash suites of this context are
+    /// supported by [risc0_zkp::core::hash::hash_suite_from_name], in which case each thread
+    /// verifies with a copy of this context. Otherwise, receipts are verified one at a time.
+    #[cfg(all(feature = "std", not(target_os = "zkvm")))]
+    pub fn verify_batch(
+        &self,
+        image_id: impl Into<Digest>,
+        receipts: &[Receipt],
+    ) -> Vec<Result<(), VerificationError>> {
+        use risc0_zkp::core::hash::hash_suite_from_name;
+
+        let image_id = image_id.into();
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(receipts.len());
+        let suite_names: Vec<&str> = self.suites.keys().map(String::as_str).collect();
+        if threads <= 1
+            || !suite_names
+                .iter()
+                .all(|name| hash_suite_from_name(name).is_some())
+        {
+            return receipts
+                .iter()
+                .map(|receipt| receipt.verify_with_context(self, image_id))
+                .collect();
+        }
+
+        // HashSuite is not Send, so each thread builds its own context from the parameters.
+        let params = (
+            self.segment_verifier_parameters.clone(),
+            self.succinct_verifier_parameters.clone(),
+            self.groth16_verifier_parameters.clone(),
+            self.allowed_versions.clone(),
+        );
+        std::thread::scope(|scope| {
+            receipts
+                .chunks(receipts.len().div_ceil(threads))
+                .map(|chunk| {
+                    let (suite_names, (segment, succinct, groth16, allowed_versions)) =
+                        (&suite_names, params.clone());
+                    scope.spawn(move || {
+                        let ctx = VerifierContext {
+                            suites: suite_names
+                                .iter()
+                                .map(|name| (name.to_string(), hash_suite_from_name(name).unwrap()))
+                                .collect(),
+                            segment_verifier_parameters: segment,
+                            succinct_verifier_parameters: succinct,
+                            groth16_verifier_parameters: groth16,
+                            allowed_versions,
+                        };
+                        chunk
+                            .iter()
+                            .map(|receipt| receipt.verify_with_context(&ctx, image_id))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("verifier thread panicked"))
+                .collect()
+        })
+    }
 }
 
 impl Default for VerifierContext {
@@ -788,19 +1359,157 @@ impl Default for VerifierContext {
             segment_verifier_parameters: Some(Default::default()),
             succinct_verifier_parameters: Some(Default::default()),
             groth16_verifier_parameters: Some(Default::default()),
+            allowed_versions: Vec::new(),
+        }
+    }
+}
+
+/// Verifier parameters for each kind of receipt under one version of the zkVM protocol.
+///
+/// The default is the version of this release. See [VerifierContext::with_allowed_versions].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ProtocolVersion {
+    /// Parameters for verification of [SegmentReceipt].
+    pub segment: SegmentReceiptVerifierParameters,
+
+    /// Parameters for verification of [SuccinctReceipt].
+    pub succinct: SuccinctReceiptVerifierParameters,
+
+    /// Parameters for verification of [Groth16Receipt].
+    pub groth16: Groth16ReceiptVerifierParameters,
+}
+
+impl ProtocolVersion {
+    /// Construct a [ProtocolVersion] from the verifier parameters of each receipt kind.
+    pub fn new(
+        segment: SegmentReceiptVerifierParameters,
+        succinct: SuccinctReceiptVerifierParameters,
+        groth16: Groth16ReceiptVerifierParameters,
+    ) -> Self {
+        Self {
+            segment,
+            succinct,
+            groth16,
+        }
+    }
+
+    /// Digest of the verifier parameters a receipt of the same kind as `inner` would carry if it
+    /// was produced under this version.
+    fn verifier_parameters(&self, inner: &InnerReceipt) -> Digest {
+        match inner {
+            InnerReceipt::Composite(_) => CompositeReceiptVerifierParameters {
+                segment: self.segment.clone().into(),
+                succinct: self.succinct.clone().into(),
+                groth16: self.groth16.clone().into(),
+            }
+            .digest(),
+            InnerReceipt::Succinct(_) => self.succinct.digest(),
+            InnerReceipt::Groth16(_) => self.groth16.digest(),
+            InnerReceipt::Fake(_) => Digest::ZERO,
         }
     }
 }
 
+/// A serializable snapshot of the parameters needed to verify receipts.
+///
+/// A [VerifierContext] holds hash suites, which cannot be serialized, and its defaults are the
+/// constants of the version of this crate it was built with. A bundle captures the same
+/// configuration by value, so that a verifier can be pinned to it and shipped independently of
+/// later upgrades of this crate. Hash suites are recorded by name, and resolved with
+/// [risc0_zkp::core::hash::hash_suite_from_name] when the bundle is used.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct VerifierBundle {
+    /// Names of the hash suites receipts may be produced with.
+    pub hash_suites: Vec<String>,
+
+    /// Parameters for verification of receipts produced under the version of this bundle.
+    pub version: ProtocolVersion,
+
+    /// Other protocol versions a receipt may have been produced under.
+    ///
+    /// See [VerifierContext::with_allowed_versions].
+    pub allowed_versions: Vec<ProtocolVersion>,
+}
+
+impl VerifierBundle {
+    /// Capture the default verifier configuration of this version of the crate.
+    pub fn current() -> Self {
+        Self {
+            hash_suites: VerifierContext::default_hash_suites().into_keys().collect(),
+            version: ProtocolVersion::default(),
+            allowed_versions: Vec::new(),
+        }
+    }
+
+    /// Construct a [VerifierContext] with the parameters of this bundle.
+    ///
+    /// Returns [VerificationError::InvalidHashSuite] if a hash suite is not supported by this
+    /// build.
+    pub fn context(&self) -> Result<VerifierContext, VerificationError> {
+        let suites = self
+            .hash_suites
+            .iter()
+            .map(|name| {
+                let suite = risc0_zkp::core::hash::hash_suite_from_name(name)
+                    .ok_or(VerificationError::InvalidHashSuite)?;
+                Ok((name.clone(), suite))
+            })
+            .collect::<Result<_, VerificationError>>()?;
+        Ok(VerifierContext {
+            suites,
+            segment_verifier_parameters: Some(self.version.segment.clone()),
+            succinct_verifier_parameters: Some(self.version.succinct.clone()),
+            groth16_verifier_parameters: Some(self.version.groth16.clone()),
+            allowed_versions: self.allowed_versions.clone(),
+        })
+    }
+
+    /// Verify that `receipt` is a valid receipt of a successful execution of the guest with
+    /// `image_id`, using the parameters of this bundle.
+    ///
+    /// This is equivalent to [Receipt::verify_with_context] with [VerifierBundle::context].
+    pub fn verify(
+        &self,
+        receipt: &Receipt,
+        image_id: impl Into<Digest>,
+    ) -> Result<(), VerificationError> {
+        receipt.verify_with_context(&self.context()?, image_id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{FakeReceipt, InnerReceipt, Receipt};
+    use super::{FakeReceipt, InnerReceipt, Receipt, VerifierBundle, VerifierContext};
     use crate::{
-        sha::{Digest, DIGEST_BYTES},
+        sha::{Digest, Digestible, DIGEST_BYTES},
         MaybePruned,
     };
     use risc0_zkp::verify::VerificationError;
 
+    #[test]
+    fn verifier_bundle() {
+        let bundle = VerifierBundle::current();
+        let loaded: VerifierBundle =
+            bincode::deserialize(&bincode::serialize(&bundle).unwrap()).unwrap();
+
+        let ctx = loaded.context().unwrap();
+        let expected = VerifierContext::default();
+        assert!(ctx.suites.keys().eq(expected.suites.keys()));
+        assert_eq!(
+            ctx.composite_verifier_parameters().unwrap().digest(),
+            expected.composite_verifier_parameters().unwrap().digest()
+        );
+
+        let mut unsupported = loaded;
+        unsupported.hash_suites.push("md5".into());
+        assert_eq!(
+            unsupported.context().err().unwrap(),
+            VerificationError::InvalidHashSuite
+        );
+    }
+
     #[test]
     fn mangled_version_info_should_error() {
         let mut mangled_receipt = Receipt::new(
@@ -841,6 +1550,234 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_against_malformed_elf() {
+        let mut receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt {
+                claim: MaybePruned::Pruned(Digest::ZERO),
+            }),
+            vec![],
+        );
+        receipt.metadata.verifier_parameters = Digest::from([1u8; DIGEST_BYTES]);
+
+        // The ELF is rejected before the mismatched verifier parameters are noticed.
+        let err = receipt.verify_against_elf(b"not an elf").unwrap_err();
+        assert!(err.downcast_ref::<VerificationError>().is_none());
+    }
+
+    #[test]
+    fn exit_code() {
+        use crate::{ExitCode, ReceiptClaim};
+        use risc0_zkvm_methods::MULTI_TEST_ID;
+
+        let receipt = |claim| Receipt::new(InnerReceipt::Fake(FakeReceipt { claim }), vec![]);
+
+        let claim = ReceiptClaim::ok(MULTI_TEST_ID, vec![]);
+        assert_eq!(
+            receipt(MaybePruned::Value(claim)).exit_code().unwrap(),
+            ExitCode::Halted(0)
+        );
+
+        let claim = ReceiptClaim {
+            exit_code: ExitCode::Paused(3),
+            ..ReceiptClaim::paused(MULTI_TEST_ID, vec![])
+        };
+        assert_eq!(
+            receipt(MaybePruned::Value(claim)).exit_code().unwrap(),
+            ExitCode::Paused(3)
+        );
+
+        assert_eq!(
+            receipt(MaybePruned::Pruned(Digest::ZERO))
+                .exit_code()
+                .unwrap_err(),
+            VerificationError::ReceiptFormatError
+        );
+    }
+
+    #[test]
+    fn verify_batch() {
+        use crate::{ReceiptClaim, VerifierContext};
+        use risc0_zkvm_methods::{HELLO_COMMIT_ID, MULTI_TEST_ID};
+
+        let receipts: Vec<_> = [MULTI_TEST_ID, HELLO_COMMIT_ID, MULTI_TEST_ID]
+            .into_iter()
+            .map(|id| {
+                let claim = ReceiptClaim::ok(id, vec![]);
+                Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), vec![])
+            })
+            .collect();
+        let mut mangled = receipts[0].clone();
+        mangled.metadata.verifier_parameters = Digest::from([1u8; DIGEST_BYTES]);
+        let receipts = [receipts, vec![mangled]].concat();
+
+        let ctx = VerifierContext::default();
+        let results = ctx.verify_batch(MULTI_TEST_ID, &receipts);
+        assert_eq!(results.len(), receipts.len());
+        for (receipt, result) in receipts.iter().zip(&results) {
+            assert_eq!(result, &receipt.verify_with_context(&ctx, MULTI_TEST_ID));
+        }
+        assert_eq!(
+            results[3],
+            Err(VerificationError::VerifierParametersMismatch {
+                expected: receipts[3].inner.verifier_parameters(),
+                received: receipts[3].metadata.verifier_parameters,
+            })
+        );
+    }
+
+    #[test]
+    fn verify_claim() {
+        use crate::{sha::Digestible, ReceiptClaim, VerifierContext};
+        use risc0_zkvm_methods::{HELLO_COMMIT_ID, MULTI_TEST_ID};
+
+        let claim = ReceiptClaim::ok(MULTI_TEST_ID, vec![]);
+        let receipt = Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim.clone())), vec![]);
+        let ctx = VerifierContext::default();
+
+        let other = ReceiptClaim::ok(HELLO_COMMIT_ID, vec![]).digest();
+        assert_eq!(
+            receipt.verify_claim(other, &ctx).unwrap_err(),
+            VerificationError::ClaimDigestMismatch {
+                expected: other,
+                received: claim.digest(),
+            }
+        );
+
+        // With a matching claim, the result is that of verifying the seal.
+        assert_eq!(
+            receipt.verify_claim(claim.digest(), &ctx),
+            receipt.inner.verify_integrity_with_context(&ctx)
+        );
+    }
+
+    #[test]
+    fn resolved_assumption() {
+        use super::{CompositeReceipt, InnerAssumptionReceipt};
+        use crate::{sha::Digestible, ReceiptClaim};
+        use risc0_zkvm_methods::{HELLO_COMMIT_ID, MULTI_TEST_ID};
+
+        let assumption = ReceiptClaim::ok(HELLO_COMMIT_ID, vec![]);
+        let composite = Receipt::new(
+            InnerReceipt::Composite(CompositeReceipt {
+                segments: vec![],
+                assumption_receipts: vec![InnerAssumptionReceipt::Fake(
+                    FakeReceipt::new(assumption.clone()).into_unknown(),
+                )],
+                verifier_parameters: Digest::ZERO,
+            }),
+            vec![],
+        );
+        let found = composite.resolved_assumption(assumption.digest()).unwrap();
+        assert_eq!(found.claim_digest().unwrap(), assumption.digest());
+
+        let other = ReceiptClaim::ok(MULTI_TEST_ID, vec![]).digest();
+        assert!(composite.resolved_assumption(other).is_none());
+
+        let fake = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(assumption.clone())),
+            vec![],
+        );
+        assert!(fake.resolved_assumption(assumption.digest()).is_none());
+    }
+
+    #[test]
+    fn same_claim() {
+        use crate::{sha::Digestible, ReceiptClaim};
+        use risc0_zkvm_methods::{HELLO_COMMIT_ID, MULTI_TEST_ID};
+
+        let claim = ReceiptClaim::ok(MULTI_TEST_ID, vec![]);
+        let full = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt {
+                claim: MaybePruned::Value(claim.clone()),
+            }),
+            vec![],
+        );
+        let pruned = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt {
+                claim: MaybePruned::Pruned(claim.digest()),
+            }),
+            vec![],
+        );
+        assert_eq!(full.claim_digest().unwrap(), claim.digest());
+        assert!(full.same_claim(&pruned));
+
+        let other = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt {
+                claim: MaybePruned::Value(ReceiptClaim::ok(HELLO_COMMIT_ID, vec![])),
+            }),
+            vec![],
+        );
+        assert!(!full.same_claim(&other));
+    }
+
+    #[test]
+    fn verify_with_telemetry_reports_phase() {
+        use super::VerifyPhase;
+
+        let mut receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt {
+                claim: MaybePruned::Pruned(Digest::ZERO),
+            }),
+            vec![],
+        );
+        receipt.metadata.verifier_parameters = Digest::from([1u8; DIGEST_BYTES]);
+
+        let err = receipt
+            .verify_with_telemetry(&Default::default(), Digest::ZERO)
+            .unwrap_err();
+        assert_eq!(err.phase, VerifyPhase::Parameters);
+    }
+
+    #[test]
+    fn allowed_versions() {
+        use super::{
+            CompositeReceipt, CompositeReceiptVerifierParameters, Groth16ReceiptVerifierParameters,
+            ProtocolVersion, SegmentReceiptVerifierParameters, SuccinctReceiptVerifierParameters,
+        };
+        use crate::{sha::Digestible, VerifierContext};
+
+        // Stand in for an older release with a different set of control IDs.
+        let old = ProtocolVersion::new(
+            SegmentReceiptVerifierParameters::from_max_po2(16),
+            SuccinctReceiptVerifierParameters::from_max_po2(16),
+            Groth16ReceiptVerifierParameters::from_max_po2(16),
+        );
+        let old_digest = CompositeReceiptVerifierParameters {
+            segment: old.segment.clone().into(),
+            succinct: old.succinct.clone().into(),
+            groth16: old.groth16.clone().into(),
+        }
+        .digest();
+        let receipt = Receipt::new(
+            InnerReceipt::Composite(CompositeReceipt {
+                segments: vec![],
+                assumption_receipts: vec![],
+                verifier_parameters: old_digest,
+            }),
+            vec![],
+        );
+
+        let ctx = VerifierContext::default().with_allowed_versions([ProtocolVersion::default()]);
+        assert_eq!(
+            receipt.verify_with_context(&ctx, Digest::ZERO).unwrap_err(),
+            VerificationError::UnsupportedProtocolVersion {
+                found: old_digest,
+                allowed: vec![CompositeReceiptVerifierParameters::default().digest()],
+            }
+        );
+
+        // Once the old version is allowed, the receipt is checked against its parameters, and
+        // only fails because it has no segments.
+        let ctx = ctx.with_allowed_versions([ProtocolVersion::default(), old.clone()]);
+        let version_ctx = ctx.for_receipt_version(&receipt.inner).unwrap().unwrap();
+        assert_eq!(version_ctx.segment_verifier_parameters, Some(old.segment));
+        assert_eq!(
+            receipt.verify_with_context(&ctx, Digest::ZERO).unwrap_err(),
+            VerificationError::ReceiptFormatError
+        );
+    }
+
     #[test]
     fn borsh_serde() {
         use crate::ReceiptClaim;
@@ -61,6 +61,24 @@ pub struct CompositeReceipt {
 }
 
 impl CompositeReceipt {
+    /// Construct a [CompositeReceipt] from the receipts of the segments of an execution, in any
+    /// order, and the receipts of its assumptions.
+    ///
+    /// Segment receipts that were proven concurrently may arrive in any order. They are sorted by
+    /// [SegmentReceipt::index], and must form the contiguous sequence `0..N`.
+    pub fn from_segments(
+        segments: Vec<SegmentReceipt>,
+        assumption_receipts: Vec<InnerAssumptionReceipt>,
+        verifier_parameters: Digest,
+    ) -> Result<Self, CompositionError> {
+        let segments = segments_in_order(&segments)?.into_iter().cloned().collect();
+        Ok(Self {
+            segments,
+            assumption_receipts,
+            verifier_parameters,
+        })
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
     pub fn verify_integrity_with_context(
@@ -68,6 +86,15 @@ impl CompositeReceipt {
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
         tracing::debug!("CompositeReceipt::verify_integrity_with_context");
+        self.verify_segments_with_context(ctx)?;
+        self.verify_assumptions_with_context(ctx)
+    }
+
+    /// Verify the continuation formed by the segment receipts.
+    pub(crate) fn verify_segments_with_context(
+        &self,
+        ctx: &VerifierContext,
+    ) -> Result<(), VerificationError> {
         // Verify the continuation, by verifying every segment receipt in order.
         let (final_receipt, receipts) = self
             .segments
@@ -110,6 +137,14 @@ impl CompositeReceipt {
             }
         }
 
+        Ok(())
+    }
+
+    /// Verify that all assumptions made by the continuation are resolved by attached receipts.
+    pub(crate) fn verify_assumptions_with_context(
+        &self,
+        ctx: &VerifierContext,
+    ) -> Result<(), VerificationError> {
         // Verify all assumptions on the receipt are resolved by attached receipts.
         // Ensure that there is one receipt for every assumption. An explicit check is required
         // because zip will terminate if either iterator terminates.
@@ -236,6 +271,71 @@ impl CompositeReceipt {
     }
 }
 
+/// Error returned when segment receipts cannot be assembled into a [CompositeReceipt].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CompositionError {
+    /// The segment indices do not form the contiguous sequence `0..N`.
+    NonContiguousSegments {
+        /// The indices below the highest segment index for which there is no receipt.
+        missing: Vec<u32>,
+    },
+
+    /// More than one receipt was given for the segment with this index.
+    DuplicateSegment {
+        /// Index of the segment.
+        index: u32,
+    },
+}
+
+impl core::fmt::Display for CompositionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NonContiguousSegments { missing } => {
+                write!(
+                    f,
+                    "segment receipts are not contiguous, missing: {missing:?}"
+                )
+            }
+            Self::DuplicateSegment { index } => {
+                write!(f, "more than one receipt for segment {index}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompositionError {}
+
+/// Returns the given segment receipts in execution order, checking that their indices form the
+/// contiguous sequence `0..N`.
+pub(crate) fn segments_in_order(
+    segments: &[SegmentReceipt],
+) -> Result<Vec<&SegmentReceipt>, CompositionError> {
+    let mut sorted: Vec<_> = segments.iter().collect();
+    sorted.sort_by_key(|segment| segment.index);
+    if let Some(pair) = sorted
+        .windows(2)
+        .find(|pair| pair[0].index == pair[1].index)
+    {
+        return Err(CompositionError::DuplicateSegment {
+            index: pair[0].index,
+        });
+    }
+    let end = sorted.last().map_or(0, |segment| segment.index + 1);
+    if end as usize != sorted.len() {
+        let missing = (0..end)
+            .filter(|index| {
+                sorted
+                    .binary_search_by_key(index, |segment| segment.index)
+                    .is_err()
+            })
+            .collect();
+        return Err(CompositionError::NonContiguousSegments { missing });
+    }
+    Ok(sorted)
+}
+
 /// Verifier parameters for [CompositeReceipt][super::CompositeReceipt].
 ///
 /// [CompositeReceipt][super::CompositeReceipt] is a collection of individual receipts that
@@ -301,9 +401,9 @@ impl Default for CompositeReceiptVerifierParameters {
 
 #[cfg(test)]
 mod tests {
-    use super::CompositeReceiptVerifierParameters;
-    use crate::sha::Digestible;
-    use risc0_zkp::core::digest::digest;
+    use super::{CompositeReceipt, CompositeReceiptVerifierParameters, CompositionError};
+    use crate::{sha::Digestible, ReceiptClaim, SegmentReceipt};
+    use risc0_zkp::core::digest::{digest, Digest};
 
     // Check that the verifier parameters has a stable digest (and therefore a stable value). This struct
     // encodes parameters used in verification, and so this value should be updated if and only if
@@ -316,4 +416,37 @@ mod tests {
             digest!("d56767c98914dd6bdc45782fbe02eda0c3a3102ae28fdd70e7c55d701e5db42d")
         );
     }
+
+    fn segments(indices: &[u32]) -> Vec<SegmentReceipt> {
+        indices
+            .iter()
+            .map(|&index| SegmentReceipt {
+                seal: Vec::new(),
+                index,
+                hashfn: "poseidon2".into(),
+                verifier_parameters: Digest::ZERO,
+                claim: ReceiptClaim::ok(Digest::ZERO, Vec::new()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn from_segments() {
+        let receipt =
+            CompositeReceipt::from_segments(segments(&[2, 0, 1]), vec![], Digest::ZERO).unwrap();
+        let indices: Vec<_> = receipt.segments.iter().map(|x| x.index).collect();
+        assert_eq!(indices, [0, 1, 2]);
+
+        assert_eq!(
+            CompositeReceipt::from_segments(segments(&[3, 0]), vec![], Digest::ZERO).unwrap_err(),
+            CompositionError::NonContiguousSegments {
+                missing: vec![1, 2]
+            }
+        );
+        assert_eq!(
+            CompositeReceipt::from_segments(segments(&[0, 1, 1]), vec![], Digest::ZERO)
+                .unwrap_err(),
+            CompositionError::DuplicateSegment { index: 1 }
+        );
+    }
 }
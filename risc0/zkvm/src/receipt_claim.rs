@@ -46,6 +46,26 @@ use crate::{
 /// state (i.e. the state of memory). [ReceiptClaim] is a "Merkle-ized struct" supporting
 /// partial openings of the underlying fields from a hash commitment to the full structure. Also
 /// see [MaybePruned].
+///
+/// # Digest
+///
+/// The digest of a [ReceiptClaim], computed with [Digestible::digest](crate::sha::Digestible), is
+/// the value attested to by a receipt and checked by the verifier. It is part of the stable API
+/// and can be recomputed independently. All hashes are SHA-256, digests are encoded as their 32
+/// bytes, and integers as 4-byte little-endian words. A struct with tag `T`, field digests
+/// `d_1..d_n` and integer fields `w_1..w_m` is hashed as
+///
+/// ```text
+/// sha256(sha256(T) || d_1 || .. || d_n || w_1 || .. || w_m || n as 2-byte little-endian)
+/// ```
+///
+/// The claim is hashed with the tag `risc0.ReceiptClaim`, the field digests of `input`, `pre`,
+/// `post` and `output`, in that order, and the integer fields `sys_exit` and `user_exit` given by
+/// [ExitCode::into_pair]. A pruned field contributes its digest directly, and a `None` value
+/// contributes the zero digest. The [SystemState] fields use the tag `risc0.SystemState`, with
+/// the digest `merkle_root` and the integer `pc`, so the image ID is the digest of `pre`. The
+/// [Output] uses the tag `risc0.Output` with the digests of the journal, which is the SHA-256 of
+/// its bytes, and of the [Assumptions].
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct ReceiptClaim {
@@ -829,6 +849,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn receipt_claim_digest_vectors() {
+        let image_id =
+            Digest::from_hex("0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20")
+                .unwrap();
+        let vectors = [
+            (
+                ReceiptClaim::ok(Digest::ZERO, Vec::new()),
+                "8fdb943ec8e96e3168ef10964340b8d4c57af48b47061e3606372534987b2ee6",
+            ),
+            (
+                ReceiptClaim::ok(image_id, Vec::new()),
+                "c4c1f643bd9e0c3005e7c93c6c4e5d56e637f599cab22855063fdc0d9eb67d51",
+            ),
+            (
+                ReceiptClaim::ok(image_id, b"hello world".to_vec()),
+                "323e1c68157e3e453a15f1f818d43fc5dae2119824fcbfba4854d94812d9e780",
+            ),
+            (
+                ReceiptClaim::paused(image_id, b"hello world".to_vec()),
+                "6e4bc2eaf18a3e8d929da71d23f85f45d936ca81856a13f7f629910cd2292e17",
+            ),
+        ];
+        for (claim, expected) in vectors {
+            assert_eq!(claim.digest().to_string(), expected);
+        }
+    }
+
     #[test]
     fn merge_receipt_claim() {
         let claim = MaybePruned::Value(ReceiptClaim {